@@ -31,6 +31,7 @@ impl Default for Mode {
 }
 
 mod params;
+mod split_memo;
 mod view;
 use template::render_template;
 use view::render_view;
@@ -720,6 +721,51 @@ pub fn params_derive(
     }
 }
 
+/// Derives a `{Name}Memo` struct that splits a `Memo<{Name}>` into one
+/// [`Memo`](leptos_reactive::Memo) per field, each of which only notifies its
+/// subscribers when that particular field changes.
+///
+/// Every field must implement `Clone` and `PartialEq`. Fields marked with
+/// `#[split_memo(skip)]` are left out.
+///
+/// ```rust
+/// # use leptos::*;
+/// #[derive(Clone, PartialEq, SplitMemo)]
+/// struct AppState {
+///     count: i32,
+///     name: String,
+///     #[split_memo(skip)]
+///     cache: Vec<u8>,
+/// }
+///
+/// # run_scope(create_runtime(), |cx| {
+/// let (state, set_state) = create_signal(
+///     cx,
+///     AppState {
+///         count: 0,
+///         name: "Alice".into(),
+///         cache: vec![],
+///     },
+/// );
+/// let state = AppStateMemo::new(cx, create_memo(cx, move |_| state()));
+///
+/// // only re-runs when `count` changes, not when `name` does
+/// let count = state.count();
+/// set_state.update(|state| state.name = "Bob".into());
+/// assert_eq!(count(), 0);
+/// assert_eq!(state.name()(), "Bob");
+/// # });
+/// ```
+#[proc_macro_derive(SplitMemo, attributes(split_memo))]
+pub fn split_memo_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input) {
+        Ok(ast) => split_memo::impl_split_memo(&ast),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 pub(crate) fn attribute_value(attr: &NodeAttribute) -> &syn::Expr {
     match &attr.value {
         Some(value) => value.as_ref(),
@@ -0,0 +1,116 @@
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+pub fn impl_split_memo(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let memo_name = format_ident!("{}Memo", name);
+
+    if !ast.generics.params.is_empty() {
+        return syn::Error::new(
+            ast.generics.span(),
+            "SplitMemo cannot be derived for generic structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields,
+        _ => {
+            return syn::Error::new(
+                Span::call_site(),
+                "SplitMemo can only be derived for structs with named fields",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let mut memo_fields = Vec::new();
+    for field in fields.named.iter() {
+        match is_skipped(field) {
+            Ok(true) => {}
+            Ok(false) => memo_fields.push(field),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+
+    let field_decls = memo_fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote! { #ident: ::leptos::Memo<#ty> }
+    });
+
+    let field_inits = memo_fields.iter().map(|field| {
+        let ident = &field.ident;
+        quote! { #ident: memo.map(cx, |state| state.#ident.clone()) }
+    });
+
+    let getters = memo_fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        let doc = format!(
+            "A memo of the `{}` field, which only notifies when that field \
+             changes.",
+            ident.as_ref().unwrap()
+        );
+        quote! {
+            #[doc = #doc]
+            #vis fn #ident(&self) -> ::leptos::Memo<#ty> {
+                self.#ident
+            }
+        }
+    });
+
+    let struct_doc = format!("Per-field memos derived from a `Memo<{name}>`.");
+    let new_doc = format!(
+        "Splits a `Memo<{name}>` into one memo for each of its fields."
+    );
+
+    let gen = quote! {
+        #[doc = #struct_doc]
+        #[derive(Clone, Copy)]
+        #vis struct #memo_name {
+            #(#field_decls,)*
+        }
+
+        impl #memo_name {
+            #[doc = #new_doc]
+            #vis fn new(
+                cx: ::leptos::Scope,
+                memo: ::leptos::Memo<#name>,
+            ) -> Self {
+                Self {
+                    #(#field_inits,)*
+                }
+            }
+
+            #(#getters)*
+        }
+    };
+    gen.into()
+}
+
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in field.attrs.iter() {
+        if !attr.path.is_ident("split_memo") {
+            continue;
+        }
+        let ident: syn::Ident = attr.parse_args()?;
+        if ident == "skip" {
+            skip = true;
+        } else {
+            return Err(syn::Error::new(
+                ident.span(),
+                "expected `#[split_memo(skip)]`",
+            ));
+        }
+    }
+    Ok(skip)
+}
@@ -0,0 +1,48 @@
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+
+#[derive(Clone, PartialEq)]
+struct Inner {
+    value: u8,
+}
+
+#[derive(Clone, PartialEq, SplitMemo)]
+struct AppState {
+    count: i32,
+    inner: Inner,
+    #[split_memo(skip)]
+    _cache: Vec<u8>,
+}
+
+#[test]
+fn split_memo() {
+    run_scope(create_runtime(), |cx| {
+        let (state, set_state) = create_signal(
+            cx,
+            AppState {
+                count: 0,
+                inner: Inner { value: 1 },
+                _cache: vec![],
+            },
+        );
+        let state = AppStateMemo::new(cx, create_memo(cx, move |_| state()));
+
+        let count = state.count();
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = runs.clone();
+            move |_| {
+                count();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        set_state.update(|state| state.inner.value = 2);
+        assert_eq!(state.inner()().value, 2);
+        assert_eq!(runs.get(), 1);
+
+        set_state.update(|state| state.count = 1);
+        assert_eq!(count(), 1);
+        assert_eq!(runs.get(), 2);
+    });
+}
@@ -0,0 +1,181 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_signal, on_cleanup, spawn_local, Memo, Scope, ScopeProperty,
+    SignalSet, SignalWith, SignalWithUntracked,
+};
+use futures::future::{abortable, AbortHandle};
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+/// Creates a memo that tracks `source`, and whenever it changes, runs
+/// `fetcher` with its new value and holds the state of the result.
+///
+/// The memo is [AsyncState::Loading] while a fetch is in progress, then
+/// [AsyncState::Ready] or [AsyncState::Failed] once it completes. If `source`
+/// changes before the previous fetch has completed, that fetch is cancelled,
+/// so an old result never replaces a newer one. Dependents only re-run when the
+/// state actually changes: loading again while already loading, or finishing
+/// with a value equal to the previous one, does not notify them.
+///
+/// Unlike a [Resource](crate::Resource), this is not serialized from the
+/// server or integrated with `<Suspense/>`, and it fetches on the server as
+/// well as the client.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (id, set_id) = create_signal(cx, 1);
+/// let user = create_resource_memo(
+///     cx,
+///     move || id(),
+///     |id| async move {
+///         if id > 0 {
+///             Ok(format!("user {id}"))
+///         } else {
+///             Err("invalid id")
+///         }
+///     },
+/// );
+/// # if !cfg!(any(feature = "csr", feature = "hydrate", feature = "ssr")) {
+/// assert_eq!(user(), AsyncState::Ready("user 1".to_string()));
+///
+/// set_id(0);
+/// assert_eq!(user(), AsyncState::Failed("invalid id"));
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_resource_memo<S, T, E, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Memo<AsyncState<T, E>>
+where
+    S: 'static,
+    T: PartialEq + 'static,
+    E: 'static,
+    Fu: Future<Output = Result<T, E>> + 'static,
+{
+    // new states are passed to the memo through a signal, which the memo
+    // takes them out of, so that they never need to be cloned
+    let (mailbox, set_mailbox) = create_signal(cx, Some(AsyncState::Loading));
+    let state =
+        cx.runtime
+            .create_owning_memo(move |prev: Option<AsyncState<T, E>>| {
+                mailbox.with(|_| ());
+                let next = mailbox
+                    .id
+                    .update_with_no_effect(
+                        cx.runtime,
+                        |next: &mut Option<_>| next.take(),
+                    )
+                    .flatten();
+                match (prev, next) {
+                    (Some(prev), Some(next)) => {
+                        let changed = !prev.is_same_as(&next);
+                        (next, changed)
+                    }
+                    (Some(prev), None) => (prev, false),
+                    (None, next) => (next.unwrap_or(AsyncState::Loading), true),
+                }
+            });
+    let state = state.owned_by(cx);
+
+    let in_flight = Rc::new(RefCell::new(None::<AbortHandle>));
+    let id = cx.runtime.create_effect({
+        let in_flight = Rc::clone(&in_flight);
+        move |_| {
+            let source = source();
+            cx.untrack(|| {
+                if let Some(prev) = in_flight.borrow_mut().take() {
+                    prev.abort();
+                }
+                if !state.with_untracked(AsyncState::is_loading) {
+                    set_mailbox.set(Some(AsyncState::Loading));
+                }
+
+                let (fut, handle) = abortable(fetcher(source));
+                *in_flight.borrow_mut() = Some(handle);
+                spawn_local(async move {
+                    if let Ok(result) = fut.await {
+                        set_mailbox.set(Some(result.into()));
+                    }
+                });
+            })
+        }
+    });
+    cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(id)));
+    on_cleanup(cx, move || {
+        if let Some(prev) = in_flight.borrow_mut().take() {
+            prev.abort();
+        }
+    });
+
+    state
+}
+
+/// The state of an `async` computation, held by a memo created with
+/// [create_resource_memo].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AsyncState<T, E> {
+    /// The computation has not completed yet.
+    Loading,
+    /// The computation completed successfully.
+    Ready(T),
+    /// The computation failed.
+    Failed(E),
+}
+
+impl<T, E> AsyncState<T, E> {
+    /// Whether the computation has not completed yet.
+    pub fn is_loading(&self) -> bool {
+        matches!(self, AsyncState::Loading)
+    }
+
+    /// The value, if the computation completed successfully.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            AsyncState::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The error, if the computation failed.
+    pub fn failed(&self) -> Option<&E> {
+        match self {
+            AsyncState::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Errors can't be compared, so a failure is never the same as another
+    /// state.
+    fn is_same_as(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        match (self, other) {
+            (AsyncState::Loading, AsyncState::Loading) => true,
+            (AsyncState::Ready(a), AsyncState::Ready(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for AsyncState<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => AsyncState::Ready(value),
+            Err(error) => AsyncState::Failed(error),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_memo, store_value, Memo, Scope, SignalGet, SignalWith, StoredValue,
+};
+
+/// Creates a [Binding], which connects a reactive value to a function that
+/// writes changes back to its source, as you would for a form control.
+///
+/// Writing through the binding is guarded against feedback loops: if writing
+/// a value causes an effect to set the binding again before the write has
+/// finished, that nested write is ignored. Setting the binding to the value it
+/// already holds does nothing.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (celsius, set_celsius) = create_signal(cx, 100.0);
+/// let fahrenheit = create_binding(
+///     cx,
+///     Signal::derive(cx, move || celsius() * 9.0 / 5.0 + 32.0),
+///     move |f: f64| set_celsius((f - 32.0) * 5.0 / 9.0),
+/// );
+///
+/// assert_eq!(fahrenheit.get(), 212.0);
+/// fahrenheit.set(32.0);
+/// assert_eq!(celsius(), 0.0);
+/// assert_eq!(fahrenheit.get(), 32.0);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_binding<T>(
+    cx: Scope,
+    read: impl SignalWith<T> + 'static,
+    write: impl Fn(T) + 'static,
+) -> Binding<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    Binding {
+        value: create_memo(cx, move |_| read.with(T::clone)),
+        write: store_value(cx, Box::new(write)),
+        writing: store_value(cx, false),
+    }
+}
+
+/// A two-way binding between a reactive value and a setter, created with
+/// [create_binding].
+///
+/// Like the signal types, `Binding` is [Copy] and `'static`, so it can
+/// easily be moved into closures.
+pub struct Binding<T>
+where
+    T: 'static,
+{
+    value: Memo<T>,
+    write: StoredValue<Box<dyn Fn(T)>>,
+    writing: StoredValue<bool>,
+}
+
+impl<T> Clone for Binding<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Binding<T> {}
+
+impl<T> std::fmt::Debug for Binding<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Binding")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T> Binding<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    /// Clones the current value, and subscribes to it when called within an
+    /// effect.
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    /// Writes `value` back to the source, unless it is equal to the current
+    /// value or the binding is already in the middle of a write.
+    pub fn set(&self, value: T) {
+        if self.writing.get_value()
+            || self.value.map_untracked(|current| *current == value)
+        {
+            return;
+        }
+
+        self.writing.set_value(true);
+        let _writing = WritingGuard(self.writing);
+        self.write.with_value(|write| write(value));
+    }
+
+    /// The bound value, as a memo.
+    pub fn value(&self) -> Memo<T> {
+        self.value
+    }
+}
+
+/// Clears a binding’s `writing` flag when dropped, so that the binding keeps
+/// working even if a write panics.
+struct WritingGuard(StoredValue<bool>);
+
+impl Drop for WritingGuard {
+    fn drop(&mut self) {
+        _ = self.0.try_set_value(false);
+    }
+}
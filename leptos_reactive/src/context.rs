@@ -16,6 +16,11 @@ use std::{
 /// the component tree can be used lower down, but a context that is provided lower
 /// in the tree cannot be used higher up.
 ///
+/// Because the [Scope](crate::Scope) is passed in explicitly, a memo or effect
+/// that calls `use_context` resolves it against the scope its closure captured,
+/// no matter which scope triggered the update that causes it to re-run, or
+/// whether it re-runs later in a deferred flush.
+///
 /// ```
 /// use leptos::*;
 ///
@@ -0,0 +1,98 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_rw_signal, RwSignal, Scope, Signal, SignalGetUntracked, SignalSet,
+};
+
+/// Creates a [DeferredCommit], a pair of signals for editing a value without
+/// publishing the edits until they are explicitly committed.
+///
+/// This is useful for patterns like inline editing with an explicit save, or
+/// “commit on blur” for form fields: bind the input to the
+/// [draft](DeferredCommit::draft), and read the rest of the UI from
+/// [committed](DeferredCommit::committed).
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let name = create_deferred_commit(cx, "Alice".to_string());
+///
+/// // ✅ editing the draft doesn't change the committed value
+/// name.draft().set("Bob".to_string());
+/// assert_eq!(name.committed()(), "Alice");
+///
+/// name.commit();
+/// assert_eq!(name.committed()(), "Bob");
+///
+/// // reverting throws away uncommitted edits
+/// name.draft().set("Carol".to_string());
+/// name.revert();
+/// assert_eq!(name.draft()(), "Bob");
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_deferred_commit<T>(cx: Scope, initial: T) -> DeferredCommit<T>
+where
+    T: Clone + 'static,
+{
+    DeferredCommit {
+        draft: create_rw_signal(cx, initial.clone()),
+        committed: create_rw_signal(cx, initial),
+    }
+}
+
+/// A draft value and a committed value, created with [create_deferred_commit].
+///
+/// Like the signal types, `DeferredCommit` is [Copy] and `'static`, so it can
+/// easily be moved into closures.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeferredCommit<T>
+where
+    T: 'static,
+{
+    draft: RwSignal<T>,
+    committed: RwSignal<T>,
+}
+
+impl<T> Clone for DeferredCommit<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DeferredCommit<T> {}
+
+impl<T> DeferredCommit<T>
+where
+    T: Clone + 'static,
+{
+    /// The editable draft value.
+    pub fn draft(&self) -> RwSignal<T> {
+        self.draft
+    }
+
+    /// The last committed value.
+    pub fn committed(&self) -> Signal<T> {
+        self.committed.into()
+    }
+
+    /// Copies the current draft into the committed value.
+    pub fn commit(&self) {
+        self.committed.set(self.draft.get_untracked());
+    }
+
+    /// Resets the draft to the last committed value.
+    pub fn revert(&self) {
+        self.draft.set(self.committed.get_untracked());
+    }
+}
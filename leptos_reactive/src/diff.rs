@@ -0,0 +1,148 @@
+#![forbid(unsafe_code)]
+use crate::{Memo, Scope};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/// Creates a memo that tracks a keyed collection, and holds what changed
+/// between its previous value and its latest value.
+///
+/// Each time `items` changes, its items are identified by `key`, and compared
+/// with the keys of the previous collection. Dependents are only notified if
+/// something was added, removed, or moved; otherwise, the memo keeps the
+/// previous [CollectionDiff]. The first computation reports every key as
+/// added.
+///
+/// Keys should be unique within a collection.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (items, set_items) = create_signal(cx, vec!["a", "b", "c"]);
+/// let diff = create_keyed_diff(cx, move || items(), |item| *item);
+/// assert_eq!(diff.with(|diff| diff.added.clone()), ["a", "b", "c"]);
+///
+/// set_items(vec!["c", "a", "d"]);
+/// assert_eq!(
+///     diff(),
+///     CollectionDiff {
+///         added: vec!["d"],
+///         removed: vec!["b"],
+///         moved: vec!["c", "a"],
+///     }
+/// );
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_keyed_diff<T, K>(
+    cx: Scope,
+    items: impl Fn() -> Vec<T> + 'static,
+    key: impl Fn(&T) -> K + 'static,
+) -> Memo<CollectionDiff<K>>
+where
+    T: 'static,
+    K: Eq + Hash + Clone + 'static,
+{
+    let prev_keys = RefCell::new(Vec::new());
+    let memo = cx.runtime.create_owning_memo(move |prev| {
+        let keys = items().iter().map(&key).collect::<Vec<_>>();
+        let diff = CollectionDiff::new(&prev_keys.borrow(), &keys);
+        *prev_keys.borrow_mut() = keys;
+        if diff.is_empty() {
+            (prev.unwrap_or_default(), false)
+        } else {
+            (diff, true)
+        }
+    });
+    memo.owned_by(cx)
+}
+
+/// The changes between two keyed collections, created by [create_keyed_diff].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollectionDiff<K> {
+    /// Keys that are in the new collection but not the old one, in the order
+    /// they appear in the new collection.
+    pub added: Vec<K>,
+    /// Keys that were in the old collection but not the new one, in the order
+    /// they appeared in the old collection.
+    pub removed: Vec<K>,
+    /// Keys that are in both collections, but whose position has changed
+    /// relative to the other keys in both, in the order they appear in the new
+    /// collection. Keys that only shift because of additions or removals are
+    /// not considered moved.
+    pub moved: Vec<K>,
+}
+
+impl<K> Default for CollectionDiff<K> {
+    fn default() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            moved: Vec::new(),
+        }
+    }
+}
+
+impl<K> CollectionDiff<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn new(old: &[K], new: &[K]) -> Self {
+        let old_set = old.iter().collect::<HashSet<_>>();
+        let new_set = new.iter().collect::<HashSet<_>>();
+
+        let added = new
+            .iter()
+            .filter(|key| !old_set.contains(key))
+            .cloned()
+            .collect();
+        let removed = old
+            .iter()
+            .filter(|key| !new_set.contains(key))
+            .cloned()
+            .collect();
+
+        // compare the positions of the keys in both, ignoring additions
+        // and removals
+        let old_positions = old
+            .iter()
+            .filter(|key| new_set.contains(key))
+            .enumerate()
+            .map(|(idx, key)| (key, idx))
+            .collect::<HashMap<_, _>>();
+        let moved = new
+            .iter()
+            .filter(|key| old_set.contains(key))
+            .enumerate()
+            .filter(|(idx, key)| old_positions.get(key) != Some(idx))
+            .map(|(_, key)| key.clone())
+            .collect();
+
+        Self {
+            added,
+            removed,
+            moved,
+        }
+    }
+}
+
+impl<K> CollectionDiff<K> {
+    /// Whether nothing was added, removed, or moved.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+    }
+}
@@ -1,11 +1,12 @@
 #![forbid(unsafe_code)]
 use crate::{
     macros::debug_warn,
+    on_cleanup,
     runtime::{with_runtime, RuntimeId},
     Runtime, Scope, ScopeProperty,
 };
 use cfg_if::cfg_if;
-use std::{cell::RefCell, fmt::Debug};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
 /// Effects run a certain chunk of code whenever the signals they depend on change.
 /// `create_effect` immediately runs the given function once, tracks its dependence
@@ -78,6 +79,163 @@ where
     }
 }
 
+/// Creates an effect that only tracks the signals read in `deps`.
+///
+/// The value returned by `deps` is passed to `f`, along with the value `f` returned
+/// the last time it ran. Anything `f` reads is untracked, so calling a helper
+/// that happens to read other signals will not cause the effect to re-run.
+///
+/// If `defer` is `true`, `f` does not run immediately; it only runs when one of
+/// the dependencies changes. In that case, the first time `f` runs it receives
+/// `None` as its previous value.
+///
+/// Like [create_effect], this **does not run on the server**.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (a, set_a) = create_signal(cx, 0);
+/// let (b, set_b) = create_signal(cx, 0);
+/// let log = Rc::new(RefCell::new(Vec::new()));
+///
+/// create_effect_on(
+///     cx,
+///     // ✅ only `a` is tracked...
+///     move || a(),
+///     {
+///         let log = Rc::clone(&log);
+///         // ...even though `b` is read here
+///         move |a, _| log.borrow_mut().push((a, b()))
+///     },
+///     true,
+/// );
+///
+/// # if !cfg!(feature = "ssr") {
+/// // deferred, so it hasn't run yet
+/// assert_eq!(*log.borrow(), vec![]);
+///
+/// set_b(1);
+/// assert_eq!(*log.borrow(), vec![]);
+///
+/// set_a(1);
+/// assert_eq!(*log.borrow(), vec![(1, 1)]);
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_effect_on<D, T>(
+    cx: Scope,
+    deps: impl Fn() -> D + 'static,
+    f: impl Fn(D, Option<T>) -> T + 'static,
+    defer: bool,
+) where
+    D: 'static,
+    T: 'static,
+{
+    create_effect(cx, move |prev: Option<Option<T>>| {
+        let deps = deps();
+        match prev {
+            None if defer => None,
+            prev => Some(cx.untrack(|| f(deps, prev.flatten()))),
+        }
+    });
+}
+
+/// Creates an effect that returns a value, like a subscription handle, along
+/// with a `cleanup` function that tears that value down.
+///
+/// `f` runs reactively, like [create_effect]. Before each time it re-runs,
+/// `cleanup` is called with the value returned by its previous run, and the
+/// value from the final run is cleaned up exactly once when the scope is
+/// disposed. This keeps a resource and its teardown together, rather than
+/// handling the previous value at the start of the next run.
+///
+/// Like [create_effect], this **does not run on the server**.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (channel, set_channel) = create_signal(cx, "news");
+/// let log = Rc::new(RefCell::new(Vec::new()));
+///
+/// create_effect_with_cleanup(
+///     cx,
+///     {
+///         let log = Rc::clone(&log);
+///         move || {
+///             log.borrow_mut().push(format!("subscribe {}", channel()));
+///             channel()
+///         }
+///     },
+///     {
+///         let log = Rc::clone(&log);
+///         move |channel| {
+///             log.borrow_mut().push(format!("unsubscribe {channel}"))
+///         }
+///     },
+/// );
+///
+/// set_channel("sports");
+/// # if !cfg!(feature = "ssr") {
+/// assert_eq!(
+///     *log.borrow(),
+///     ["subscribe news", "unsubscribe news", "subscribe sports"]
+/// );
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<C>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_effect_with_cleanup<C>(
+    cx: Scope,
+    f: impl Fn() -> C + 'static,
+    cleanup: impl Fn(C) + 'static,
+) where
+    C: 'static,
+{
+    let cleanup = Rc::new(cleanup);
+    let current = Rc::new(RefCell::new(None::<C>));
+    create_effect(cx, {
+        let cleanup = Rc::clone(&cleanup);
+        let current = Rc::clone(&current);
+        move |_| {
+            let prev = current.borrow_mut().take();
+            if let Some(prev) = prev {
+                cx.untrack(|| cleanup(prev));
+            }
+            let value = f();
+            *current.borrow_mut() = Some(value);
+        }
+    });
+    on_cleanup(cx, move || {
+        let value = current.borrow_mut().take();
+        if let Some(value) = value {
+            cleanup(value);
+        }
+    });
+}
+
 /// Creates an effect; unlike effects created by [create_effect], isomorphic effects will run on
 /// the server as well as the client.
 /// ```
@@ -104,6 +262,10 @@ where
 /// });
 /// # assert_eq!(b(), 2);
 /// # }).dispose();
+/// ```
+///
+/// Cleanup functions registered with [on_cleanup](crate::on_cleanup) run when the
+/// scope is disposed, in exactly the same way on the server and in the browser.
 #[cfg_attr(
     debug_assertions,
     instrument(
@@ -163,6 +325,9 @@ where
 
 pub(crate) trait AnyEffect {
     fn run(&self, id: EffectId, runtime: RuntimeId);
+
+    #[cfg(debug_assertions)]
+    fn defined_at(&self) -> &'static std::panic::Location<'static>;
 }
 
 impl<T, F> AnyEffect for Effect<T, F>
@@ -201,6 +366,11 @@ where
             runtime.observer.set(prev_observer);
         })
     }
+
+    #[cfg(debug_assertions)]
+    fn defined_at(&self) -> &'static std::panic::Location<'static> {
+        self.defined_at
+    }
 }
 
 impl EffectId {
@@ -239,8 +409,8 @@ impl EffectId {
         let sources = runtime.effect_sources.borrow();
         if let Some(sources) = sources.get(*self) {
             let subs = runtime.signal_subscribers.borrow();
-            for source in sources.borrow().iter() {
-                if let Some(source) = subs.get(*source) {
+            for source in sources.borrow_mut().drain() {
+                if let Some(source) = subs.get(source) {
                     source.borrow_mut().remove(self);
                 }
             }
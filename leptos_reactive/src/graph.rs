@@ -0,0 +1,271 @@
+#![forbid(unsafe_code)]
+use crate::{effect::EffectId, runtime::Runtime, SignalId};
+#[cfg(debug_assertions)]
+use crate::{runtime::with_runtime, Scope};
+use slotmap::Key;
+#[cfg(debug_assertions)]
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    rc::Rc,
+};
+
+/// Shown by [Scope::lookup_value] for nodes whose value can’t be printed.
+pub(crate) const OPAQUE: &str = "<opaque>";
+
+/// Identifies a node in the reactive graph: a signal, a memo, or an effect.
+///
+/// Node ids are stable for the lifetime of the node, and can be printed to
+/// label nodes in diagnostics like [Memo::dependencies](crate::Memo::dependencies).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(NodeKind);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum NodeKind {
+    Signal(SignalId),
+    Memo(SignalId),
+    #[cfg_attr(not(debug_assertions), allow(dead_code))]
+    Effect(EffectId),
+}
+
+impl NodeId {
+    pub(crate) fn memo(id: SignalId) -> Self {
+        NodeId(NodeKind::Memo(id))
+    }
+
+    /// Whether this node is a signal.
+    pub fn is_signal(&self) -> bool {
+        matches!(self.0, NodeKind::Signal(_))
+    }
+
+    /// Whether this node is a memo.
+    pub fn is_memo(&self) -> bool {
+        matches!(self.0, NodeKind::Memo(_))
+    }
+
+    /// Whether this node is an effect.
+    pub fn is_effect(&self) -> bool {
+        matches!(self.0, NodeKind::Effect(_))
+    }
+}
+
+impl std::fmt::Display for NodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            NodeKind::Signal(id) => write!(f, "Signal({:?})", id.data()),
+            NodeKind::Memo(id) => write!(f, "Memo({:?})", id.data()),
+            NodeKind::Effect(id) => write!(f, "Effect({:?})", id.data()),
+        }
+    }
+}
+
+impl Runtime {
+    /// The node for a signal, which is a memo if the signal holds a memo’s value.
+    pub(crate) fn signal_node(&self, id: SignalId) -> NodeId {
+        if self.memo_effect_ids.borrow().contains_key(id) {
+            NodeId::memo(id)
+        } else {
+            NodeId(NodeKind::Signal(id))
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    /// The node for an effect, which is a memo if the effect computes a memo.
+    pub(crate) fn effect_node(&self, id: EffectId) -> NodeId {
+        match self.memo_effects.borrow().get(&id) {
+            Some(memo) => NodeId::memo(*memo),
+            None => NodeId(NodeKind::Effect(id)),
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    /// The signals and memos read by a memo the last time it computed.
+    pub(crate) fn memo_dependencies(&self, memo: SignalId) -> Vec<NodeId> {
+        let sources = self
+            .memo_effect(memo)
+            .and_then(|effect| {
+                self.effect_sources
+                    .borrow()
+                    .get(effect)
+                    .map(|sources| sources.borrow().clone())
+            })
+            .unwrap_or_default();
+        let mut nodes = sources
+            .into_iter()
+            .map(|source| self.signal_node(source))
+            .collect::<Vec<_>>();
+        nodes.sort();
+        nodes
+    }
+
+    #[cfg(debug_assertions)]
+    /// The memos and effects currently subscribed to a memo.
+    pub(crate) fn memo_subscribers(&self, memo: SignalId) -> Vec<NodeId> {
+        let subscribers = self
+            .signal_subscribers
+            .borrow()
+            .get(memo)
+            .map(|subs| subs.borrow().clone())
+            .unwrap_or_default();
+        let mut nodes = subscribers
+            .into_iter()
+            .map(|sub| self.effect_node(sub))
+            .collect::<Vec<_>>();
+        nodes.sort();
+        nodes
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Scope {
+    /// Renders every signal, memo, and effect in this scope’s runtime as a
+    /// [Graphviz](https://graphviz.org/) DOT graph, with an edge from each node
+    /// to the memos and effects that subscribe to it.
+    ///
+    /// Memos and effects are labeled with the location at which they were
+    /// created. This is only available in debug builds, and is intended for
+    /// pasting into a graph viewer to spot unexpectedly large fan-out or
+    /// accidental cycles.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    ///
+    /// let dot = cx.graph_to_dot();
+    /// assert!(dot.starts_with("digraph"));
+    /// assert!(dot.contains("->"));
+    /// # }).dispose();
+    /// ```
+    pub fn graph_to_dot(&self) -> String {
+        with_runtime(self.runtime, |runtime| runtime.graph_to_dot())
+            .unwrap_or_default()
+    }
+
+    /// Renders the current value of any signal or memo in this scope’s
+    /// runtime, given its [NodeId], or `None` if the node no longer exists.
+    ///
+    /// Only values of signals and memos that have been marked
+    /// [debuggable](crate::ReadSignal::debuggable) are printed with their
+    /// [Debug] implementation; other values, and effects, are shown as
+    /// `"<opaque>"`. This is only available in debug builds.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let count = count.debuggable();
+    /// let double = create_memo(cx, move |_| count() * 2).debuggable();
+    ///
+    /// set_count(2);
+    /// assert_eq!(cx.lookup_value(count.node_id()).as_deref(), Some("2"));
+    /// assert_eq!(cx.lookup_value(double.node_id()).as_deref(), Some("4"));
+    /// # }).dispose();
+    /// ```
+    pub fn lookup_value(&self, id: NodeId) -> Option<String> {
+        with_runtime(self.runtime, |runtime| runtime.lookup_value(id))
+            .ok()
+            .flatten()
+    }
+}
+
+impl Runtime {
+    /// Lets [Scope::lookup_value] print the value of a signal with `format`.
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    pub(crate) fn set_value_formatter<T: 'static>(
+        &self,
+        id: SignalId,
+        format: impl Fn(&T) -> String + 'static,
+    ) {
+        #[cfg(debug_assertions)]
+        self.value_formatters.borrow_mut().insert(
+            id,
+            Rc::new(move |value: &dyn Any| match value.downcast_ref::<T>() {
+                Some(value) => format(value),
+                None => OPAQUE.to_string(),
+            }),
+        );
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Runtime {
+    fn lookup_value(&self, id: NodeId) -> Option<String> {
+        let id = match id.0 {
+            NodeKind::Signal(id) | NodeKind::Memo(id) => id,
+            NodeKind::Effect(id) => {
+                return self
+                    .effects
+                    .borrow()
+                    .contains_key(id)
+                    .then(|| OPAQUE.to_string())
+            }
+        };
+        let value = self.signals.borrow().get(id).cloned()?;
+        let format = self.value_formatters.borrow().get(id).cloned();
+        let value = value.borrow();
+        Some(match format {
+            Some(format) => format(&*value),
+            None => OPAQUE.to_string(),
+        })
+    }
+    fn graph_to_dot(&self) -> String {
+        let memos = self
+            .memo_effects
+            .borrow()
+            .iter()
+            .map(|(effect, memo)| (*memo, *effect))
+            .collect::<HashMap<_, _>>();
+        let node = |id: SignalId| match memos.contains_key(&id) {
+            true => NodeId::memo(id),
+            false => NodeId(NodeKind::Signal(id)),
+        };
+
+        let mut nodes = self
+            .signals
+            .borrow()
+            .keys()
+            .map(node)
+            .chain(
+                self.effects
+                    .borrow()
+                    .keys()
+                    .map(|id| self.effect_node(id))
+                    .filter(NodeId::is_effect),
+            )
+            .collect::<Vec<_>>();
+        nodes.sort();
+
+        let mut edges = HashSet::new();
+        for (effect, sources) in self.effect_sources.borrow().iter() {
+            let target = self.effect_node(effect);
+            for source in sources.borrow().iter() {
+                edges.insert((node(*source), target));
+            }
+        }
+        let mut edges = edges.into_iter().collect::<Vec<_>>();
+        edges.sort();
+
+        let mut dot = String::from("digraph {\n");
+        for id in nodes {
+            let (shape, effect) = match id.0 {
+                NodeKind::Signal(_) => ("ellipse", None),
+                NodeKind::Memo(memo) => ("box", memos.get(&memo).copied()),
+                NodeKind::Effect(effect) => ("diamond", Some(effect)),
+            };
+            let defined_at = effect
+                .and_then(|effect| self.effects.borrow().get(effect).cloned())
+                .map(|effect| format!("\\n{}", effect.defined_at()))
+                .unwrap_or_default();
+            _ = writeln!(
+                dot,
+                "    \"{id}\" [label=\"{id}{defined_at}\", shape={shape}];"
+            );
+        }
+        for (source, target) in edges {
+            _ = writeln!(dot, "    \"{source}\" -> \"{target}\";");
+        }
+        dot.push('}');
+        dot
+    }
+}
@@ -71,29 +71,46 @@ extern crate tracing;
 
 #[macro_use]
 mod signal;
+mod async_state;
+mod binding;
 mod context;
+mod deferred_commit;
+mod diff;
 mod effect;
+mod graph;
 mod hydration;
 mod memo;
 mod resource;
+mod retry;
 mod runtime;
+mod sample;
 mod scope;
 mod selector;
 mod serialization;
 mod signal_wrappers_read;
 mod signal_wrappers_write;
 mod slice;
+mod snapshot;
 mod spawn;
 mod spawn_microtask;
+mod standalone;
+mod stepper;
 mod stored_value;
 pub mod suspense;
 
+pub use async_state::*;
+pub use binding::*;
 pub use context::*;
+pub use deferred_commit::*;
+pub use diff::*;
 pub use effect::*;
+pub use graph::*;
 pub use memo::*;
 pub use resource::*;
+pub use retry::*;
 use runtime::*;
-pub use runtime::{create_runtime, RuntimeId};
+pub use runtime::{create_runtime, set_scheduler, RuntimeId, SchedulerStats};
+pub use sample::*;
 pub use scope::*;
 pub use selector::*;
 pub use serialization::*;
@@ -101,8 +118,11 @@ pub use signal::{prelude as signal_prelude, *};
 pub use signal_wrappers_read::*;
 pub use signal_wrappers_write::*;
 pub use slice::*;
+pub use snapshot::*;
 pub use spawn::*;
 pub use spawn_microtask::*;
+pub use standalone::*;
+pub use stepper::*;
 pub use stored_value::*;
 pub use suspense::SuspenseContext;
 
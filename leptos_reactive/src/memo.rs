@@ -1,61 +1,1204 @@
 #![forbid(unsafe_code)]
 use crate::{
-    create_effect, on_cleanup, ReadSignal, Scope, SignalGet,
-    SignalGetUntracked, SignalStream, SignalWith, SignalWithUntracked,
+    create_effect, create_signal,
+    graph::OPAQUE,
+    on_cleanup,
+    runtime::{with_runtime, MemoInspector, RuntimeId},
+    spawn::{set_timeout, sleep},
+    EffectId, NodeId, ReadSignal, Scope, ScopeProperty, Serializable,
+    SerializationError, Signal, SignalError, SignalGet, SignalGetUntracked,
+    SignalId, SignalSet, SignalStream, SignalWith, SignalWithUntracked,
 };
-use std::fmt::Debug;
+use cfg_if::cfg_if;
+use std::{
+    any::{Any, TypeId},
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    future::Future,
+    hash::Hash,
+    pin::Pin,
+    rc::{Rc, Weak},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Creates an efficient derived reactive value based on other reactive values.
+///
+/// Unlike a "derived signal," a memo comes with two guarantees:
+/// 1. The memo will only run *once* per change, no matter how many times you
+/// access its value.
+/// 2. The memo will only notify its dependents if the value of the computation changes.
+///
+/// This makes a memo the perfect tool for expensive computations.
+///
+/// Memos have a certain overhead compared to derived signals. In most cases, you should
+/// create a derived signal. But if the derivation calculation is expensive, you should
+/// create a memo.
+///
+/// As with [create_effect](crate::create_effect), the argument to the memo function is the previous value,
+/// i.e., the current value of the memo, which will be `None` for the initial calculation.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # fn really_expensive_computation(value: i32) -> i32 { value };
+/// # create_scope(create_runtime(), |cx| {
+/// let (value, set_value) = create_signal(cx, 0);
+///
+/// // 🆗 we could create a derived signal with a simple function
+/// let double_value = move || value() * 2;
+/// set_value(2);
+/// assert_eq!(double_value(), 4);
+///
+/// // but imagine the computation is really expensive
+/// let expensive = move || really_expensive_computation(value()); // lazy: doesn't run until called
+/// create_effect(cx, move |_| {
+///   // 🆗 run #1: calls `really_expensive_computation` the first time
+///   log::debug!("expensive = {}", expensive());
+/// });
+/// create_effect(cx, move |_| {
+///   // ❌ run #2: this calls `really_expensive_computation` a second time!
+///   let value = expensive();
+///   // do something else...
+/// });
+///
+/// // instead, we create a memo
+/// // 🆗 run #1: the calculation runs once immediately
+/// let memoized = create_memo(cx, move |_| really_expensive_computation(value()));
+/// create_effect(cx, move |_| {
+///  // 🆗 reads the current value of the memo
+///   log::debug!("memoized = {}", memoized());
+/// });
+/// create_effect(cx, move |_| {
+///   // ✅ reads the current value **without re-running the calculation**
+///   let value = memoized();
+///   // do something else...
+/// });
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo<T>(
+    cx: Scope,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: PartialEq + 'static,
+{
+    cx.runtime.create_memo(f).owned_by(cx)
+}
+
+/// Returns the memo registered under `key` in this [Scope], creating it with `f`
+/// if no memo has been registered under that key yet.
+///
+/// This allows several call sites that need the same derived computation to
+/// share a single memo, rather than each creating and running their own.
+/// Memos are only shared within the same scope, and the registry is cleared
+/// when the scope is disposed.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 1);
+/// let runs = Rc::new(Cell::new(0));
+///
+/// let make_double = || {
+///     let runs = Rc::clone(&runs);
+///     create_shared_memo(cx, "double", move || {
+///         create_memo(cx, move |_| {
+///             runs.set(runs.get() + 1);
+///             count() * 2
+///         })
+///     })
+/// };
+///
+/// // ✅ both call sites get the same memo, which only computes once
+/// let a = make_double();
+/// let b = make_double();
+/// assert_eq!(a, b);
+/// assert_eq!(runs.get(), 1);
+///
+/// set_count(2);
+/// assert_eq!(a(), 4);
+/// assert_eq!(b(), 4);
+/// assert_eq!(runs.get(), 2);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+pub fn create_shared_memo<K, T>(
+    cx: Scope,
+    key: K,
+    f: impl FnOnce() -> Memo<T>,
+) -> Memo<T>
+where
+    K: Eq + Hash + 'static,
+    T: 'static,
+{
+    let registry = TypeId::of::<HashMap<K, Memo<T>>>();
+
+    let existing = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .scope_shared_memos
+            .borrow()
+            .get(cx.id)
+            .and_then(|memos| memos.get(&registry))
+            .and_then(|memos| memos.downcast_ref::<HashMap<K, Memo<T>>>())
+            .and_then(|memos| memos.get(&key).copied())
+    })
+    .ok()
+    .flatten();
+
+    if let Some(memo) = existing {
+        return memo;
+    }
+
+    let memo = f();
+    _ = with_runtime(cx.runtime, |runtime| {
+        let mut shared_memos = runtime.scope_shared_memos.borrow_mut();
+        if let Some(memos) = shared_memos.entry(cx.id) {
+            let memos =
+                memos.or_default().entry(registry).or_insert_with(|| {
+                    Box::<HashMap<K, Memo<T>>>::default() as Box<dyn Any>
+                });
+            if let Some(memos) = memos.downcast_mut::<HashMap<K, Memo<T>>>() {
+                memos.insert(key, memo);
+            }
+        }
+    });
+    memo
+}
+
+/// Creates a memo that always notifies its dependents when it recomputes, rather
+/// than only when its value has changed.
+///
+/// This keeps the first guarantee of [create_memo] (the computation runs only
+/// once per change, no matter how many times its value is read) but drops the
+/// second, which allows it to be used for values that cannot implement
+/// [PartialEq], like boxed closures or trait objects.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (factor, set_factor) = create_signal(cx, 2);
+///
+/// // ✅ `Box<dyn Fn>` does not implement `PartialEq`
+/// let scale = create_memo_always(cx, move |_| {
+///     let factor = factor();
+///     Box::new(move |n: i32| n * factor) as Box<dyn Fn(i32) -> i32>
+/// });
+///
+/// assert_eq!(scale.with(|scale| scale(3)), 6);
+/// set_factor(3);
+/// assert_eq!(scale.with(|scale| scale(3)), 9);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_always<T>(
+    cx: Scope,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+{
+    cx.runtime.create_memo_with_eq(f, |_, _| false).owned_by(cx)
+}
+
+/// Creates a memo like [create_memo], but with a computation that can mutate
+/// its own state, without needing a [Cell](std::cell::Cell) or [RefCell].
+///
+/// Memos never run their computation more than once at a time, so `f` is
+/// always called with exclusive access to its state. `f` must not cause the
+/// same memo to recompute while it is running (for example, by setting a
+/// signal the memo depends on): this panics.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (name, set_name) = create_signal(cx, "Alice");
+///
+/// // ✅ a sequence number that increments each time the memo runs
+/// let mut seq = 0;
+/// let versioned = create_memo_mut(cx, move |_| {
+///     seq += 1;
+///     (seq, name())
+/// });
+///
+/// assert_eq!(versioned(), (1, "Alice"));
+/// set_name("Bob");
+/// assert_eq!(versioned(), (2, "Bob"));
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_mut<T>(
+    cx: Scope,
+    f: impl FnMut(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: PartialEq + 'static,
+{
+    let f = RefCell::new(f);
+    cx.runtime
+        .create_memo(move |prev| {
+            let mut f = f.try_borrow_mut().expect(
+                "a memo created with create_memo_mut cannot recompute while \
+                 it is already running",
+            );
+            f(prev)
+        })
+        .owned_by(cx)
+}
+
+/// Creates a memo whose computation takes ownership of its previous value,
+/// which allows large values like collections to be updated in place rather
+/// than reallocated each time the memo runs.
+///
+/// `f` receives the previous value by value, which will be `None` for the
+/// initial calculation, and returns the new value along with whether it has
+/// changed. If it returns `false`, the value is still stored, but dependents
+/// are not notified, so it is up to `f` to report changes accurately.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (filter, set_filter) = create_signal(cx, 0);
+///
+/// // ✅ reuses the same buffer every time the filter changes
+/// let evens = create_owning_memo(cx, move |prev: Option<Vec<i32>>| {
+///     let mut buf = prev.unwrap_or_default();
+///     let len = buf.len();
+///     buf.clear();
+///     buf.extend((0..10).filter(|n| n % 2 == 0 && *n >= filter()));
+///     let changed = buf.len() != len;
+///     (buf, changed)
+/// });
+///
+/// assert_eq!(evens.with(Vec::len), 5);
+/// set_filter(5);
+/// assert_eq!(evens.with(Vec::clone), [6, 8]);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_owning_memo<T>(
+    cx: Scope,
+    f: impl Fn(Option<T>) -> (T, bool) + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+{
+    cx.runtime.create_owning_memo(f).owned_by(cx)
+}
+
+/// Creates a memo that decides whether it has changed by comparing a cheap
+/// key computed from each value, rather than comparing the values themselves.
+///
+/// Each time the memo runs, `hash` is applied to the new value, and dependents
+/// are only notified if the result differs from the one computed for the
+/// previous value. This is useful for large values where `PartialEq` is
+/// expensive, like long strings or nested maps, if you can cheaply derive
+/// something like a content hash or a version number.
+///
+/// **Note**: If two different values produce the same key (for example, a
+/// hash collision, or a version number that was not bumped), the memo stores
+/// the new value but does **not** notify its dependents, which will continue to
+/// show the old value until the next change.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (doc, set_doc) = create_signal(cx, (1, "a long document".to_string()));
+///
+/// // ✅ only compares the version number, not the whole text
+/// let text = create_memo_hashed(
+///     cx,
+///     |(version, _): &(usize, String)| *version,
+///     move |_| doc(),
+/// );
+///
+/// set_doc((2, "a longer document".to_string()));
+/// assert_eq!(text.with(|(_, text)| text.len()), 17);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_hashed<T, H>(
+    cx: Scope,
+    hash: impl Fn(&T) -> H + 'static,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+    H: PartialEq + 'static,
+{
+    let prev_hash = RefCell::new(None);
+    cx.runtime
+        .create_owning_memo(move |prev| {
+            let new = f(prev.as_ref());
+            let new_hash = hash(&new);
+            let changed = prev_hash.borrow().as_ref() != Some(&new_hash);
+            *prev_hash.borrow_mut() = Some(new_hash);
+            (new, changed)
+        })
+        .owned_by(cx)
+}
+
+/// Creates a memo that only notifies its dependents when a projection of its
+/// value changes, like a record’s `id`, ignoring changes to everything else.
+///
+/// Each time the memo runs, `key` is applied to both the new value and the
+/// previous one, and dependents are only notified if the keys differ. The new
+/// value is always stored, so reading the memo returns the latest value, even
+/// if only fields outside the key have changed. Unlike [create_memo_hashed],
+/// the key is recomputed from the stored value rather than remembered, so it
+/// is always an exact comparison of the two values’ projections.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone)]
+/// struct User {
+///     id: u32,
+///     last_seen: u64,
+/// }
+///
+/// let (user, set_user) = create_signal(
+///     cx,
+///     User {
+///         id: 1,
+///         last_seen: 0,
+///     },
+/// );
+/// // ✅ only notifies when a different user is selected
+/// let selected =
+///     create_memo_eq_by(cx, |user: &User| user.id, move |_| user());
+///
+/// set_user(User {
+///     id: 1,
+///     last_seen: 10,
+/// });
+/// assert_eq!(selected.with(|user| user.last_seen), 10);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_eq_by<T, K>(
+    cx: Scope,
+    key: impl Fn(&T) -> K + 'static,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+    K: PartialEq + 'static,
+{
+    cx.runtime
+        .create_owning_memo(move |prev| {
+            let new = f(prev.as_ref());
+            let changed = prev.as_ref().map(&key) != Some(key(&new));
+            (new, changed)
+        })
+        .owned_by(cx)
+}
+
+/// Creates a memo that only runs its computation in the browser. On the
+/// server, it holds `placeholder` and never runs `f`, which is useful for
+/// values that depend on browser-only APIs.
+///
+/// When hydrating, the memo starts out with `placeholder`, so that it matches
+/// what was rendered on the server, and runs `f` as soon as the current
+/// hydration pass has finished, notifying its dependents if the result is
+/// different. In any other client-side build, it is a normal memo created with
+/// [create_memo], and `placeholder` is not used.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (width, set_width) = create_signal(cx, 1024);
+/// let is_mobile = create_memo_client_only(cx, false, move |_| width() < 768);
+///
+/// # if !cfg!(feature = "ssr") {
+/// set_width(375);
+/// assert!(is_mobile());
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_client_only<T>(
+    cx: Scope,
+    placeholder: T,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: PartialEq + 'static,
+{
+    cfg_if! {
+        if #[cfg(feature = "ssr")] {
+            _ = f;
+            let placeholder = RefCell::new(Some(placeholder));
+            cx.runtime.create_owning_memo(move |prev| {
+                let value = prev
+                    .or_else(|| placeholder.borrow_mut().take())
+                    .expect("a client-only memo always holds its placeholder");
+                (value, false)
+            })
+            .owned_by(cx)
+        } else if #[cfg(feature = "hydrate")] {
+            let memo = cx.runtime.create_seeded_memo(
+                Some(placeholder),
+                f,
+                |prev, new| prev == new,
+            );
+            crate::queue_microtask(move || {
+                _ = with_runtime(cx.runtime, |runtime| {
+                    runtime.run_seeded_memo(memo.0.id, cx.runtime)
+                });
+            });
+            memo.owned_by(cx)
+        } else {
+            _ = placeholder;
+            cx.runtime.create_memo(f).owned_by(cx)
+        }
+    }
+}
+
+/// Creates a memo whose value is stored in an [Arc], so that reading it with
+/// [Memo::get_arc] (or `.get()`) is a cheap reference-count increment rather
+/// than a deep clone.
+///
+/// `f` receives a reference to the previous value. Each new value is compared
+/// to the previous value with [PartialEq] before it is wrapped, so a new [Arc]
+/// is only allocated, and dependents are only notified, if the value has
+/// actually changed. This is useful for large, immutable snapshots that are
+/// read by many subscribers.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::sync::Arc;
+/// # create_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 3);
+/// let records = create_memo_arc(cx, move |_| vec![0; count()]);
+///
+/// let a = records.get_arc();
+/// let b = records.get_arc();
+/// // ✅ both point to the same vector
+/// assert!(Arc::ptr_eq(&a, &b));
+///
+/// set_count(4);
+/// assert_eq!(records.get_arc().len(), 4);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_arc<T>(
+    cx: Scope,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<Arc<T>>
+where
+    T: PartialEq + 'static,
+{
+    cx.runtime
+        .create_owning_memo(move |prev: Option<Arc<T>>| {
+            let new = f(prev.as_deref());
+            match prev {
+                Some(prev) if *prev == new => (prev, false),
+                _ => (Arc::new(new), true),
+            }
+        })
+        .owned_by(cx)
+}
+
+/// Creates a memo that starts with the value `initial`, rather than running
+/// `f` when it is created, and runs `f` once `deps` changes.
+///
+/// This is useful when the full computation is expensive, but there is a
+/// cheap default that is good enough until something changes. Because `f` has
+/// not run, the memo can’t discover its dependencies by running it, so, like
+/// [create_effect_on](crate::create_effect_on), it only tracks the signals
+/// read by `deps`, and passes their value to `f` along with the memo’s
+/// previous value. Anything `f` reads itself is not tracked. If the first
+/// computation returns a value equal to `initial`, dependents are not
+/// notified.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (items, set_items) = create_signal(cx, vec![1, 2, 3]);
+///
+/// // starts out empty, without sorting anything
+/// let sorted = create_memo_with_initial(
+///     cx,
+///     Vec::new(),
+///     move || items(),
+///     |mut items, _| {
+///         items.sort();
+///         items
+///     },
+/// );
+/// assert!(sorted.with(Vec::is_empty));
+///
+/// set_items(vec![3, 1, 2]);
+/// assert_eq!(sorted(), vec![1, 2, 3]);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_with_initial<D, T>(
+    cx: Scope,
+    initial: T,
+    deps: impl Fn() -> D + 'static,
+    f: impl Fn(D, Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    D: 'static,
+    T: PartialEq + 'static,
+{
+    let initial = RefCell::new(Some(initial));
+    cx.runtime
+        .create_memo(move |prev: Option<&T>| {
+            let deps = deps();
+            match initial.take() {
+                Some(initial) => initial,
+                None => cx.untrack(|| f(deps, prev)),
+            }
+        })
+        .owned_by(cx)
+}
+
+/// Sets a hook that is called whenever a memo’s computation panics, with the
+/// memo’s [NodeId] and the panic payload, which is usually a `&str` or a
+/// `String`. Only one hook can be set for each runtime; setting another one
+/// replaces it.
+///
+/// Once the hook has run, the memo recovers by keeping its previous value:
+/// its dependents are not notified, and it will try to compute again the next
+/// time any of the signals it read before panicking changes. If the memo has
+/// no previous value to keep, because the panic happened in its first
+/// computation or because it took ownership of its previous value (as in
+/// [create_owning_memo]), the panic continues to unwind after the hook runs.
+///
+/// Without a hook, panics in memos unwind as usual.
+///
+/// This requires the `catch-panics` feature, and has no effect if panics
+/// abort rather than unwind, as is the default for `wasm32` targets.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// set_reactive_panic_hook(cx, |node, payload| {
+///     let message = payload
+///         .downcast_ref::<&str>()
+///         .copied()
+///         .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+///         .unwrap_or("<unknown>");
+///     log::error!("{node} panicked: {message}");
+/// });
+///
+/// let (input, set_input) = create_signal(cx, "1".to_string());
+/// let number = create_memo(cx, move |_| input().parse::<i32>().unwrap());
+///
+/// set_input("oops".to_string());
+/// // the memo keeps its last value
+/// assert_eq!(number(), 1);
+/// # }).dispose();
+/// ```
+#[cfg(feature = "catch-panics")]
+pub fn set_reactive_panic_hook(
+    cx: Scope,
+    hook: impl Fn(NodeId, &(dyn Any + Send)) + 'static,
+) {
+    _ = with_runtime(cx.runtime, |runtime| {
+        *runtime.panic_hook.borrow_mut() = Some(std::rc::Rc::new(hook));
+    });
+}
+
+/// Creates a memo that follows `on_true` while `cond` returns `true`, and
+/// `on_false` otherwise.
+///
+/// The memo only subscribes to `cond` and the active branch, so changes to the
+/// inactive branch do not cause it to re-run. When `cond` flips, it switches
+/// its subscription to the other branch, and only notifies its dependents if
+/// the value it now holds is different.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (use_celsius, set_use_celsius) = create_signal(cx, true);
+/// let (celsius, set_celsius) = create_signal(cx, 20.0);
+/// let celsius = create_memo(cx, move |_| celsius());
+/// let fahrenheit = create_memo(cx, move |_| celsius() * 9.0 / 5.0 + 32.0);
+///
+/// let temperature =
+///     create_switch_memo(cx, move || use_celsius(), celsius, fahrenheit);
+/// assert_eq!(temperature(), 20.0);
+///
+/// set_use_celsius(false);
+/// assert_eq!(temperature(), 68.0);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_switch_memo<T>(
+    cx: Scope,
+    cond: impl Fn() -> bool + 'static,
+    on_true: Memo<T>,
+    on_false: Memo<T>,
+) -> Memo<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    cx.runtime
+        .create_memo(move |_| {
+            if cond() {
+                on_true.get()
+            } else {
+                on_false.get()
+            }
+        })
+        .owned_by(cx)
+}
+
+/// Creates a memo that only updates when `f` returns `Some(_)`, and otherwise
+/// keeps its previous value.
+///
+/// This is useful when deriving a value from input that may be invalid, like
+/// parsing user input: invalid states are ignored, and the memo holds on to
+/// the last valid value. If the first computation returns `None`, the memo
+/// holds `initial`. As with [create_memo], dependents are only notified when
+/// the value actually changes.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (input, set_input) = create_signal(cx, "12".to_string());
+/// let number = create_filter_map_memo(cx, 0, move || input().parse().ok());
+/// assert_eq!(number(), 12);
+///
+/// // ❌ invalid input is ignored...
+/// set_input("12a".to_string());
+/// assert_eq!(number(), 12);
+///
+/// // ✅ ...until the input is valid again
+/// set_input("123".to_string());
+/// assert_eq!(number(), 123);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_filter_map_memo<T>(
+    cx: Scope,
+    initial: T,
+    f: impl Fn() -> Option<T> + 'static,
+) -> Memo<T>
+where
+    T: PartialEq + 'static,
+{
+    let initial = RefCell::new(Some(initial));
+    cx.runtime
+        .create_owning_memo(move |prev| match (f(), prev) {
+            (Some(new), prev) => {
+                let changed = prev.as_ref() != Some(&new);
+                (new, changed)
+            }
+            (None, Some(prev)) => (prev, false),
+            (None, None) => (
+                initial
+                    .borrow_mut()
+                    .take()
+                    .expect("initial value is only used once"),
+                true,
+            ),
+        })
+        .owned_by(cx)
+}
+
+/// Creates a memo over a floating-point computation that only notifies its
+/// dependents when the value moves by more than `epsilon`.
+///
+/// This avoids re-running downstream effects for changes that are effectively
+/// noise. The comparison is always made against the last value that was
+/// actually stored, so a series of small changes that adds up to more than
+/// `epsilon` will still be picked up.
+///
+/// Unlike [PartialEq] for `f64`, two `NaN` values are treated as equal, so a
+/// computation that keeps producing `NaN` does not notify on every run.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (reading, set_reading) = create_signal(cx, 1.0);
+/// let smoothed = create_memo_approx(cx, 0.1, move |_| reading());
+///
+/// let notifications = Rc::new(Cell::new(0));
+/// create_isomorphic_effect(cx, {
+///     let notifications = Rc::clone(&notifications);
+///     move |_| {
+///         smoothed();
+///         notifications.set(notifications.get() + 1);
+///     }
+/// });
+///
+/// // within epsilon: the memo keeps its previous value
+/// set_reading(1.05);
+/// assert_eq!(smoothed(), 1.0);
+/// assert_eq!(notifications.get(), 1);
+///
+/// // further than epsilon from the stored value: notifies
+/// set_reading(1.2);
+/// assert_eq!(smoothed(), 1.2);
+/// assert_eq!(notifications.get(), 2);
+///
+/// // NaN is considered equal to NaN
+/// set_reading(f64::NAN);
+/// set_reading(f64::NAN);
+/// assert_eq!(notifications.get(), 3);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_approx(
+    cx: Scope,
+    epsilon: f64,
+    f: impl Fn(Option<&f64>) -> f64 + 'static,
+) -> Memo<f64> {
+    cx.runtime
+        .create_memo_with_eq(f, move |prev, new| {
+            if prev.is_nan() || new.is_nan() {
+                prev.is_nan() && new.is_nan()
+            } else {
+                prev == new || (new - prev).abs() <= epsilon
+            }
+        })
+        .owned_by(cx)
+}
+
+/// Creates a memo over a pair of source signals that caches every result it
+/// has computed, keyed by the values of both sources.
+///
+/// Whenever either key changes, the memo looks up the `(A, B)` combination in
+/// its cache and only calls `compute` if it has never seen that combination
+/// before. This is useful when users tend to toggle back and forth between a
+/// small number of expensive states.
+///
+/// The cache lives as long as the memo and is never evicted, so this should
+/// only be used when the number of distinct key combinations is bounded.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (a, set_a) = create_signal(cx, 1);
+/// let (b, set_b) = create_signal(cx, 10);
+/// let runs = Rc::new(Cell::new(0));
+///
+/// let product = create_multi_keyed_memo(cx, (a.into(), b.into()), {
+///     let runs = Rc::clone(&runs);
+///     move |a, b| {
+///         runs.set(runs.get() + 1);
+///         a * b
+///     }
+/// });
+/// assert_eq!(product(), 10);
+///
+/// set_a(2);
+/// set_b(20);
+/// assert_eq!(product(), 40);
+/// assert_eq!(runs.get(), 3);
+///
+/// // ✅ returning to a previously-seen combination uses the cached value
+/// set_a(1);
+/// set_b(10);
+/// assert_eq!(product(), 10);
+/// assert_eq!(runs.get(), 4);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+        )
+    )
+)]
+#[track_caller]
+pub fn create_multi_keyed_memo<A, B, V>(
+    cx: Scope,
+    keys: (Signal<A>, Signal<B>),
+    compute: impl Fn(&A, &B) -> V + 'static,
+) -> Memo<V>
+where
+    A: Clone + Eq + Hash + 'static,
+    B: Clone + Eq + Hash + 'static,
+    V: Clone + PartialEq + 'static,
+{
+    let (a, b) = keys;
+    let cache = RefCell::new(HashMap::<(A, B), V>::new());
+
+    create_memo(cx, move |_| {
+        a.with(|a| {
+            b.with(|b| {
+                let key = (a.clone(), b.clone());
+                if let Some(cached) = cache.borrow().get(&key) {
+                    return cached.clone();
+                }
+                let value = compute(a, b);
+                cache.borrow_mut().insert(key, value.clone());
+                value
+            })
+        })
+    })
+}
+
+/// Creates a memo whose value is a rolling window of the last `capacity`
+/// distinct values returned by `f`, oldest first and newest last.
+///
+/// Each time `f` returns a value that differs from the newest value in the
+/// window, it is pushed onto the back, and the oldest value is dropped once the
+/// window is full. If `f` returns the same value as before, the window does
+/// not change and the memo does not notify its dependents. This is useful for
+/// showing recent history, e.g., in a sparkline.
+///
+/// The argument to `f` is the newest value in the window, which will be `None`
+/// for the initial calculation.
+///
+/// # Panics
+/// In debug builds, panics if `capacity` is `0`.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (reading, set_reading) = create_signal(cx, 1);
+/// let history = create_memo_history(cx, 3, move |_| reading());
+///
+/// set_reading(2);
+/// // setting the same value does not add to the history
+/// set_reading(2);
+/// set_reading(3);
+/// set_reading(4);
+///
+/// assert_eq!(history.get(), [2, 3, 4]);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_memo_history<T>(
+    cx: Scope,
+    capacity: usize,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<VecDeque<T>>
+where
+    T: Clone + PartialEq + 'static,
+{
+    debug_assert!(
+        capacity > 0,
+        "create_memo_history requires a capacity of at least 1"
+    );
 
-/// Creates an efficient derived reactive value based on other reactive values.
+    create_memo(cx, move |history: Option<&VecDeque<T>>| {
+        let value = f(history.and_then(VecDeque::back));
+        match history {
+            Some(history) if history.back() == Some(&value) => history.clone(),
+            Some(history) => {
+                let mut history = history.clone();
+                if history.len() >= capacity {
+                    history.pop_front();
+                }
+                history.push_back(value);
+                history
+            }
+            None => VecDeque::from([value]),
+        }
+    })
+}
+
+/// Creates a memo that accumulates every value of `source`, like a reactive
+/// [Iterator::fold].
 ///
-/// Unlike a "derived signal," a memo comes with two guarantees:
-/// 1. The memo will only run *once* per change, no matter how many times you
-/// access its value.
-/// 2. The memo will only notify its dependents if the value of the computation changes.
+/// The memo starts by folding the current value of `source` into `initial`.
+/// Then, each time a signal read by `source` changes, the new value of
+/// `source` is folded into the previous accumulator. Reading the memo never
+/// folds again, and dependents are only notified when the new accumulator
+/// differs from the old one.
 ///
-/// This makes a memo the perfect tool for expensive computations.
+/// Unlike a signal that is [updated](crate::SignalUpdate::update) by hand
+/// whenever something happens, the fold is driven by changes to whatever
+/// `source` reads.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (price, set_price) = create_signal(cx, 10);
+/// let total = create_fold_memo(cx, 0, price, |total, price| total + price);
+/// assert_eq!(total(), 10);
 ///
-/// Memos have a certain overhead compared to derived signals. In most cases, you should
-/// create a derived signal. But if the derivation calculation is expensive, you should
-/// create a memo.
+/// set_price(15);
+/// set_price(5);
+/// assert_eq!(total(), 30);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<A>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_fold_memo<T, A>(
+    cx: Scope,
+    initial: A,
+    source: impl Fn() -> T + 'static,
+    fold: impl Fn(&A, T) -> A + 'static,
+) -> Memo<A>
+where
+    T: 'static,
+    A: PartialEq + 'static,
+{
+    create_memo(cx, move |acc| fold(acc.unwrap_or(&initial), source()))
+}
+
+/// Creates a memo that computes its value promptly, but holds back from its
+/// dependents until the current work is done, like React’s `useDeferredValue`.
 ///
-/// As with [create_effect](crate::create_effect), the argument to the memo function is the previous value,
-/// i.e., the current value of the memo, which will be `None` for the initial calculation.
+/// `f` runs as soon as its sources change, like any other memo. The deferred
+/// memo only takes on that value, and notifies its dependents, in a
+/// [microtask](crate::queue_microtask) queued after the current event. If the
+/// runtime has a [scheduler](RuntimeId::set_scheduler), that is delayed until
+/// the scheduler runs the next flush, so a scheduler backed by
+/// `requestIdleCallback` makes updates low-priority. Several changes made
+/// before then are coalesced into one update with the latest value.
 ///
+/// Reading the memo, including with `.get_untracked()`, returns the last
+/// value that was passed on, which may lag behind `f`. On the server, where
+/// microtasks run immediately, it does not lag unless a scheduler is set.
 /// ```
 /// # use leptos_reactive::*;
-/// # fn really_expensive_computation(value: i32) -> i32 { value };
 /// # create_scope(create_runtime(), |cx| {
-/// let (value, set_value) = create_signal(cx, 0);
+/// let (query, set_query) = create_signal(cx, String::new());
+/// // filtering a long list shouldn't block typing
+/// let filter = create_deferred_memo(cx, move |_| query().to_lowercase());
 ///
-/// // 🆗 we could create a derived signal with a simple function
-/// let double_value = move || value() * 2;
-/// set_value(2);
-/// assert_eq!(double_value(), 4);
+/// set_query("Leptos".to_string());
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// assert_eq!(filter(), "leptos");
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_deferred_memo<T>(
+    cx: Scope,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let latest = create_memo(cx, f);
+    let (committed, commit) = create_signal(cx, ());
+    let deferred = create_memo(cx, move |_| {
+        committed.with(|_| {});
+        latest.get_untracked()
+    });
+    // the deferred memo lags from the moment a new value is computed, even
+    // if a scheduler holds back the effect below
+    latest.inspect(move |_| {
+        _ = with_runtime(cx.runtime, |runtime| {
+            runtime.mark_memo_dirty(deferred.0.id)
+        });
+    });
+    let pending = Rc::new(Cell::new(false));
+    crate::create_isomorphic_effect(cx, move |prev: Option<()>| {
+        latest.with(|_| {});
+        if prev.is_some() && !pending.replace(true) {
+            let pending = Rc::clone(&pending);
+            crate::queue_microtask(move || {
+                pending.set(false);
+                _ = commit.try_set(());
+            });
+        }
+    });
+    deferred
+}
+
+/// Creates a memo that runs its computation at most once every
+/// `min_interval`, which protects the CPU from an expensive memo whose
+/// sources change in bursts.
 ///
-/// // but imagine the computation is really expensive
-/// let expensive = move || really_expensive_computation(value()); // lazy: doesn't run until called
-/// create_effect(cx, move |_| {
-///   // 🆗 run #1: calls `really_expensive_computation` the first time
-///   log::debug!("expensive = {}", expensive());
-/// });
-/// create_effect(cx, move |_| {
-///   // ❌ run #2: this calls `really_expensive_computation` a second time!
-///   let value = expensive();
-///   // do something else...
-/// });
+/// The memo computes its value when it is created, and again as soon as a
+/// source changes after `min_interval` has passed. A change that arrives
+/// sooner is not computed right away; instead, the memo computes once more
+/// when the interval is up, using the latest values of its sources at that
+/// point, and however many changes arrived in between. Until then, reading
+/// the memo returns the last value it computed. As with [create_memo],
+/// dependents are only notified when the computed value changes.
 ///
-/// // instead, we create a memo
-/// // 🆗 run #1: the calculation runs once immediately
-/// let memoized = create_memo(cx, move |_| really_expensive_computation(value()));
-/// create_effect(cx, move |_| {
-///  // 🆗 reads the current value of the memo
-///   log::debug!("memoized = {}", memoized());
-/// });
-/// create_effect(cx, move |_| {
-///   // ✅ reads the current value **without re-running the calculation**
-///   let value = memoized();
-///   // do something else...
-/// });
+/// On native platforms without the `ssr` feature, where
+/// [spawn_local](crate::spawn_local) blocks until the future completes, there
+/// is no cooldown at all, so the memo behaves like one created with
+/// [create_memo]. With the `ssr` feature, the cooldown is timed by tokio, so
+/// the memo has to be used inside a
+/// [`LocalSet`](https://docs.rs/tokio/latest/tokio/task/struct.LocalSet.html),
+/// just like [spawn_local](crate::spawn_local); otherwise it panics the first
+/// time it computes.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::time::Duration;
+/// # create_scope(create_runtime(), |cx| {
+/// let (pointer, set_pointer) = create_signal(cx, (0, 0));
+/// // hit-testing against a large scene is expensive, so do it 60 times a
+/// // second at most
+/// let hovered =
+///     create_throttled_memo(cx, Duration::from_millis(16), move |_| {
+///         pointer().0 / 100
+///     });
 /// # }).dispose();
 /// ```
 #[cfg_attr(
@@ -65,17 +1208,109 @@ use std::fmt::Debug;
         skip_all,
         fields(
             cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
         )
     )
 )]
-pub fn create_memo<T>(
+#[track_caller]
+pub fn create_throttled_memo<T>(
     cx: Scope,
+    min_interval: Duration,
     f: impl Fn(Option<&T>) -> T + 'static,
 ) -> Memo<T>
 where
     T: PartialEq + 'static,
 {
-    cx.runtime.create_memo(f)
+    let (trailing, run_trailing) = create_signal(cx, ());
+    let cooling = Rc::new(Cell::new(false));
+    let skipped = Rc::new(Cell::new(false));
+
+    cx.runtime
+        .create_owning_memo(move |prev: Option<T>| {
+            trailing.with(|_| {});
+            match prev {
+                // while cooling down, the memo stops tracking its sources and
+                // only waits for the trailing run
+                Some(prev) if cooling.get() => {
+                    skipped.set(true);
+                    _ = with_runtime(cx.runtime, |runtime| {
+                        if let Some(memo) = runtime.computing_memo.get() {
+                            runtime.mark_memo_dirty(memo);
+                        }
+                    });
+                    (prev, false)
+                }
+                prev => {
+                    skipped.set(false);
+                    let new = f(prev.as_ref());
+                    let changed = prev.as_ref() != Some(&new);
+
+                    cooling.set(true);
+                    set_timeout(
+                        {
+                            let cooling = Rc::clone(&cooling);
+                            let skipped = Rc::clone(&skipped);
+                            move || {
+                                cooling.set(false);
+                                if skipped.get() {
+                                    _ = run_trailing.try_set(());
+                                }
+                            }
+                        },
+                        min_interval,
+                    );
+
+                    (new, changed)
+                }
+            }
+        })
+        .owned_by(cx)
+}
+
+/// Seeds the memo with the given [SignalId] with a value that was serialized
+/// on the server using [Memo::serialize].
+///
+/// This should be called *before* the memo is created. When the memo is
+/// created, it will take the deserialized value as its initial value rather
+/// than running its computation. Because it has not run, the memo does not
+/// yet know its dependencies: in the browser, it recomputes as soon as the
+/// current hydration pass has finished, and from then on behaves like any
+/// other memo. Outside the browser, it recomputes as soon as it is created.
+/// If its dependencies differ from the server snapshot, the recomputed value
+/// replaces the seed and notifies subscribers as usual.
+///
+/// Memo IDs, like [ResourceId](crate::ResourceId)s, are assigned in creation
+/// order, so the same ID identifies the same memo on the server and client as
+/// long as they create their reactive graph in the same order.
+#[cfg(feature = "hydrate")]
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            id = ?id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+pub fn hydrate_memo_from<T>(
+    cx: Scope,
+    id: SignalId,
+    json: &str,
+) -> Result<(), SerializationError>
+where
+    T: Serializable + 'static,
+{
+    let value = T::de(json)?;
+    _ = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .memo_seeds
+            .borrow_mut()
+            .insert(id, Box::new(value) as Box<dyn Any>)
+    });
+    Ok(())
 }
 
 /// An efficient derived reactive value based on other reactive values.
@@ -106,6 +1341,10 @@ where
 ///   value without reactively tracking it.
 /// - [`.to_stream()`](#impl-SignalStream<T>-for-Memo<T>) converts the signal to an `async` stream of values.
 ///
+/// The `get` family of methods clones the value, and so is only available when `T: Clone`.
+/// The `with` family, along with [`.map()`](Memo::map) and [`.map_untracked()`](Memo::map_untracked),
+/// works for any `T`, and is the way to read a memo whose value can’t be cloned.
+///
 /// ## Examples
 /// ```
 /// # use leptos_reactive::*;
@@ -144,7 +1383,6 @@ where
 /// });
 /// # }).dispose();
 /// ```
-#[derive(Debug, PartialEq, Eq)]
 pub struct Memo<T>(
     pub(crate) ReadSignal<Option<T>>,
     #[cfg(debug_assertions)] pub(crate) &'static std::panic::Location<'static>,
@@ -152,6 +1390,27 @@ pub struct Memo<T>(
 where
     T: 'static;
 
+// memos are compared by identity, so this doesn't require `T: PartialEq`,
+// which memos created by `create_memo_always` may not implement
+impl<T> PartialEq for Memo<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.runtime == other.0.runtime && self.0.id == other.0.id
+    }
+}
+
+impl<T> Eq for Memo<T> {}
+
+// implemented by hand so that it doesn't require `T: Debug`
+impl<T> Debug for Memo<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Memo");
+        s.field("id", &self.0.id);
+        #[cfg(debug_assertions)]
+        s.field("defined_at", &self.1);
+        s.finish()
+    }
+}
+
 impl<T> Clone for Memo<T>
 where
     T: 'static,
@@ -290,7 +1549,7 @@ impl<T: Clone> SignalGet<T> for Memo<T> {
         )
     )]
     fn try_get(&self) -> Option<T> {
-        self.0.try_get().flatten()
+        self.try_get_result().ok()
     }
 }
 
@@ -326,7 +1585,7 @@ impl<T> SignalWith<T> for Memo<T> {
         )
     )]
     fn try_with<O>(&self, f: impl FnOnce(&T) -> O) -> Option<O> {
-        self.0.try_with(|t| f(t.as_ref().unwrap())).ok()
+        self.try_with_result(f).ok()
     }
 }
 
@@ -351,14 +1610,979 @@ impl<T: Clone> SignalStream<T> for Memo<T> {
     }
 }
 
+impl<T> Memo<T>
+where
+    T: Debug + 'static,
+{
+    /// Marks the memo as debuggable, so that
+    /// [Scope::lookup_value](crate::Scope::lookup_value) prints its value
+    /// rather than `"<opaque>"`. This does nothing in release builds.
+    pub fn debuggable(self) -> Self {
+        _ = with_runtime(self.0.runtime, |runtime| {
+            runtime.set_value_formatter(self.0.id, |value: &Option<T>| {
+                match value {
+                    Some(value) => format!("{value:?}"),
+                    None => OPAQUE.to_string(),
+                }
+            })
+        });
+        self
+    }
+
+    /// Panics if the memo’s current value is not equal to `expected`, with a
+    /// message showing both values side by side. The memo is read without
+    /// being tracked. This is intended for tests.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 1);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    ///
+    /// set_count(2);
+    /// double.assert_eq_untracked(&4);
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn assert_eq_untracked(&self, expected: &T)
+    where
+        T: PartialEq,
+    {
+        self.with_untracked(|value| {
+            if value != expected {
+                panic!(
+                    "{} does not have the expected value\n{}",
+                    self.node_id(),
+                    side_by_side(
+                        ("value", &format!("{value:#?}")),
+                        ("expected", &format!("{expected:#?}"))
+                    )
+                );
+            }
+        })
+    }
+
+    /// Compares the current values of two memos without tracking them, and
+    /// describes how they differ, showing both values side by side, or returns
+    /// `None` if they are the same.
+    ///
+    /// Values are compared with [PartialEq], and only printed with [Debug].
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (name, set_name) = create_signal(cx, "Alice".to_string());
+    /// let upper = create_memo(cx, move |_| name().to_uppercase());
+    /// let lower = create_memo(cx, move |_| name().to_lowercase());
+    ///
+    /// assert!(upper.diff_from(&upper).is_none());
+    /// assert!(upper.diff_from(&lower).unwrap().contains("\"alice\""));
+    /// # }).dispose();
+    /// ```
+    pub fn diff_from(&self, other: &Memo<T>) -> Option<String>
+    where
+        T: PartialEq,
+    {
+        self.with_untracked(|left| {
+            other.with_untracked(|right| {
+                (left != right).then(|| {
+                    side_by_side(
+                        (&self.node_id().to_string(), &format!("{left:#?}")),
+                        (&other.node_id().to_string(), &format!("{right:#?}")),
+                    )
+                })
+            })
+        })
+    }
+}
+
+/// Lays out two labeled, multi-line values in columns, marking the lines that
+/// differ with `>`.
+fn side_by_side(left: (&str, &str), right: (&str, &str)) -> String {
+    let left_lines = std::iter::once(left.0)
+        .chain(left.1.lines())
+        .collect::<Vec<_>>();
+    let right_lines = std::iter::once(right.0)
+        .chain(right.1.lines())
+        .collect::<Vec<_>>();
+    let width = left_lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let mut out = String::new();
+    for i in 0..left_lines.len().max(right_lines.len()) {
+        let l = left_lines.get(i).copied().unwrap_or_default();
+        let r = right_lines.get(i).copied().unwrap_or_default();
+        let marker = if i > 0 && l != r { '>' } else { ' ' };
+        out.push_str(&format!("{marker} {l:<width$} | {r}\n"));
+    }
+    out
+}
+
+impl<T> Memo<Arc<T>> {
+    /// Returns the shared value of a memo created with [create_memo_arc],
+    /// and subscribes the running effect to the memo. Unlike reading a
+    /// `Memo<T>`, this does not clone `T`.
+    #[track_caller]
+    pub fn get_arc(&self) -> Arc<T> {
+        self.with(Arc::clone)
+    }
+}
+
 impl<T> Memo<T>
 where
     T: 'static,
 {
+    /// Creates a new memo that applies `f` to this memo’s value.
+    ///
+    /// The derived memo only re-runs when this memo changes, and only notifies
+    /// its own dependents when the mapped value changes. This makes it a cheap way
+    /// to subscribe to a single part of a larger value.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (name, set_name) = create_signal(cx, "Alice".to_string());
+    /// let greeting = create_memo(cx, move |_| format!("Hello, {}!", name()));
+    /// let len = greeting.map(cx, |greeting| greeting.len());
+    ///
+    /// assert_eq!(len(), 13);
+    /// set_name("Bob".to_string());
+    /// assert_eq!(len(), 11);
+    /// # }).dispose();
+    /// ```
+    pub fn map<U>(&self, cx: Scope, f: impl Fn(&T) -> U + 'static) -> Memo<U>
+    where
+        U: PartialEq + 'static,
+    {
+        let this = *self;
+        create_memo(cx, move |_| this.with(&f))
+    }
+
+    /// Creates a new memo of a part of this memo’s value, which `f` borrows
+    /// from it, like a field or an element of a collection.
+    ///
+    /// Only the borrowed part is cloned into the derived memo, so this is a
+    /// cheap way to pick a small value out of a large one. As with
+    /// [Memo::map], the derived memo only notifies its dependents when the
+    /// part changes.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// #[derive(PartialEq)]
+    /// struct Page {
+    ///     title: String,
+    ///     body: Vec<String>,
+    /// }
+    ///
+    /// let (body, set_body) = create_signal(cx, Vec::new());
+    /// let page = create_memo(cx, move |_| Page {
+    ///     title: "Leptos".to_string(),
+    ///     body: body(),
+    /// });
+    /// let title = page.map_ref(cx, |page| &page.title);
+    ///
+    /// set_body(vec!["A fine-grained reactive framework.".to_string()]);
+    /// assert_eq!(title(), "Leptos");
+    /// # }).dispose();
+    /// ```
+    pub fn map_ref<U>(
+        &self,
+        cx: Scope,
+        f: impl Fn(&T) -> &U + 'static,
+    ) -> Memo<U>
+    where
+        U: Clone + PartialEq + 'static,
+    {
+        let this = *self;
+        create_memo(cx, move |_| this.with(|value| f(value).clone()))
+    }
+
+    /// Creates a new memo like [Memo::map], but `f` also receives this memo’s
+    /// previous value, which is `None` until this memo changes. This is useful
+    /// for computing deltas between values.
+    ///
+    /// From now on, this memo keeps the value it replaces each time it
+    /// changes, rather than dropping it, so neither value is cloned. Memos that
+    /// take ownership of their previous value to compute the next one, like
+    /// those created with [create_memo_arc], have no previous value to keep,
+    /// so `f` always receives `None` for them. The derived memo is owned by
+    /// the same scope as this memo.
+    ///
+    /// # Panics
+    /// Panics if this memo has been disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (position, set_position) = create_signal(cx, 0.0);
+    /// let position = create_memo(cx, move |_| position());
+    /// let velocity = position.map_with_prev(|prev: Option<&f64>, new: &f64| {
+    ///     prev.map(|prev| new - prev).unwrap_or_default()
+    /// });
+    ///
+    /// assert_eq!(velocity(), 0.0);
+    /// set_position(5.0);
+    /// assert_eq!(velocity(), 5.0);
+    /// set_position(3.0);
+    /// assert_eq!(velocity(), -2.0);
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn map_with_prev<U>(
+        &self,
+        f: impl Fn(Option<&T>, &T) -> U + 'static,
+    ) -> Memo<U>
+    where
+        U: PartialEq + 'static,
+    {
+        let this = *self;
+        let runtime = self.0.runtime;
+        let owner = with_runtime(runtime, |rt| {
+            rt.retain_previous_memo_value(this.0.id);
+            rt.memo_owners.borrow().get(this.0.id).copied()
+        })
+        .ok()
+        .flatten()
+        .expect("tried to map a memo that has been disposed");
+        create_memo(Scope { runtime, id: owner }, move |_| {
+            let prev = with_runtime(runtime, |rt| {
+                rt.memo_previous.borrow().get(this.0.id).cloned().flatten()
+            })
+            .ok()
+            .flatten();
+            let prev =
+                prev.as_deref().and_then(|prev| prev.downcast_ref::<T>());
+            this.with(|new| f(prev, new))
+        })
+    }
+
+    /// Applies `f` to the memo’s current value and returns the result, without
+    /// reactively tracking the memo. Unlike [`.get_untracked()`](SignalGetUntracked::get_untracked),
+    /// this does not require `T: Clone`.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// // `Vec<Box<dyn Fn() -> i32>>` can’t be cloned
+    /// let (count, set_count) = create_signal(cx, 2);
+    /// let callbacks = create_memo_always(cx, move |_| {
+    ///     (0..count())
+    ///         .map(|n| Box::new(move || n) as Box<dyn Fn() -> i32>)
+    ///         .collect::<Vec<_>>()
+    /// });
+    ///
+    /// assert_eq!(callbacks.map_untracked(|c| c.len()), 2);
+    /// set_count(3);
+    /// assert_eq!(callbacks.map_untracked(|c| c[2]()), 2);
+    /// # }).dispose();
+    /// ```
+    pub fn map_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        self.with_untracked(f)
+    }
+
+    /// Returns the memo along with a flag that is `true` for a single flush
+    /// whenever the memo changes, which is useful for driving one-shot
+    /// animations or other imperative integrations.
+    ///
+    /// The flag is set when the memo changes, and reset to `false` once the
+    /// signal update that caused the change has finished notifying all of its
+    /// subscribers. If the memo changes several times during one update, the
+    /// flag is only set once. The flag is set in the same pass that notifies
+    /// the memo’s dependents, so an effect that reads both runs once.
+    ///
+    /// The memo itself is returned unchanged. Each call creates a separate
+    /// flag, owned by `cx`, which stops updating once `cx` is disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::{cell::RefCell, rc::Rc};
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    /// let (double, changed) = double.with_changed_flag(cx);
+    ///
+    /// let pulses = Rc::new(RefCell::new(Vec::new()));
+    /// create_isomorphic_effect(cx, {
+    ///     let pulses = Rc::clone(&pulses);
+    ///     move |_| pulses.borrow_mut().push((double(), changed()))
+    /// });
+    ///
+    /// set_count(1);
+    /// assert_eq!(*pulses.borrow(), [(0, false), (2, true), (2, false)]);
+    /// assert!(!changed());
+    /// # }).dispose();
+    /// ```
+    pub fn with_changed_flag(&self, cx: Scope) -> (Memo<T>, ReadSignal<bool>) {
+        let (changed, set_changed) = create_signal(cx, false);
+        let runtime = cx.runtime;
+        let memo = self.0.id;
+        // the flag is set silently just before the memo notifies, so anything
+        // that reads both runs once, in the memo's own notification pass
+        let inspector = Rc::new(move |_: &dyn Any| {
+            if changed.try_get_untracked() != Some(false) {
+                return;
+            }
+            changed
+                .id
+                .update_with_no_effect(runtime, |changed: &mut bool| {
+                    *changed = true
+                });
+            _ = with_runtime(runtime, |rt| {
+                rt.scheduler.after_flush(move || {
+                    _ = with_runtime(runtime, |rt| {
+                        let notified = rt.subscribers(memo);
+                        rt.notify_subscribers(changed.id, runtime, &notified);
+                    });
+                    set_changed.set(false);
+                })
+            });
+        }) as Rc<MemoInspector>;
+        let inspecting = InspectorGuard {
+            runtime,
+            memo,
+            inspector: Rc::downgrade(&inspector),
+        };
+        _ = with_runtime(runtime, |rt| rt.add_memo_inspector(memo, inspector));
+        on_cleanup(cx, move || drop(inspecting));
+        (*self, changed)
+    }
+
+    /// The number of times the memo’s value has changed since it was created.
+    ///
+    /// This only increases when the memo would notify its dependents, so
+    /// recomputations that produce an equal value do not count. Storing the
+    /// version and comparing it later with [Memo::changed_since] is a cheap way
+    /// to check for changes outside the reactive system, without cloning or
+    /// comparing the value, and without subscribing to the memo.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 1);
+    /// let is_even = create_memo(cx, move |_| count() % 2 == 0);
+    /// let version = is_even.version();
+    ///
+    /// set_count(3);
+    /// assert!(!is_even.changed_since(version));
+    /// set_count(4);
+    /// assert!(is_even.changed_since(version));
+    /// # }).dispose();
+    /// ```
+    pub fn version(&self) -> u64 {
+        with_runtime(self.0.runtime, |runtime| {
+            runtime
+                .memo_versions
+                .borrow()
+                .get(self.0.id)
+                .copied()
+                .unwrap_or_default()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Whether the memo has changed since it was at the given
+    /// [version](Memo::version).
+    pub fn changed_since(&self, version: u64) -> bool {
+        self.version() != version
+    }
+
+    /// Calls `f` with each new value of the memo, for logging or collecting
+    /// metrics, and returns the same memo, like [Iterator::inspect].
+    ///
+    /// The inspector is passive: it does not subscribe to the memo or cause it
+    /// to run more often than it otherwise would. It is called whenever the
+    /// memo changes, just before its dependents are notified, but not for
+    /// recomputations that produce an equal value.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::{cell::RefCell, rc::Rc};
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    /// let double = create_memo(cx, move |_| count() * 2).inspect({
+    ///     let log = Rc::clone(&log);
+    ///     move |value| log.borrow_mut().push(*value)
+    /// });
+    ///
+    /// set_count(1);
+    /// set_count(2);
+    /// assert_eq!(*log.borrow(), [2, 4]);
+    /// # }).dispose();
+    /// ```
+    pub fn inspect(self, f: impl Fn(&T) + 'static) -> Memo<T> {
+        _ = with_runtime(self.0.runtime, |runtime| {
+            let inspector = Rc::new(move |value: &dyn Any| {
+                if let Some(value) = value.downcast_ref::<T>() {
+                    f(value);
+                }
+            }) as Rc<MemoInspector>;
+            runtime.add_memo_inspector(self.0.id, inspector);
+        });
+        self
+    }
+
+    /// Freezes the memo, detaching it from all of its sources. A frozen memo
+    /// keeps its last value permanently, and ignores changes to its sources
+    /// until it is [thawed](Memo::thaw).
+    ///
+    /// Reading a frozen memo still subscribes to it, so anything that depends
+    /// on it will update once it is thawed and its value changes.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 1);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    ///
+    /// double.freeze();
+    /// set_count(2);
+    /// assert_eq!(double(), 2);
+    ///
+    /// double.thaw();
+    /// assert_eq!(double(), 4);
+    /// # }).dispose();
+    /// ```
+    pub fn freeze(&self) {
+        _ = with_runtime(self.0.runtime, |runtime| {
+            if let Some(effect) = runtime.memo_effect(self.0.id) {
+                effect.cleanup(runtime);
+                runtime.frozen_memos.borrow_mut().insert(self.0.id);
+            }
+        });
+    }
+
+    /// Thaws a memo frozen with [Memo::freeze], recomputing it and tracking
+    /// its sources again. Calling this on a memo that is not frozen does
+    /// nothing.
+    pub fn thaw(&self) {
+        let effect = with_runtime(self.0.runtime, |runtime| {
+            if runtime.frozen_memos.borrow_mut().remove(&self.0.id) {
+                runtime.memo_effect(self.0.id)
+            } else {
+                None
+            }
+        });
+        if let Ok(Some(effect)) = effect {
+            effect.run(self.0.runtime);
+        }
+    }
+
+    /// Whether the memo has put off an update that it has yet to make, so
+    /// that its value lags behind its sources.
+    ///
+    /// Memos usually recompute as soon as a source changes, so they are only
+    /// dirty while holding back: a [deferred memo](create_deferred_memo) with
+    /// a value it has not yet passed on, a
+    /// [throttled memo](create_throttled_memo) waiting for its trailing run,
+    /// or, while hydrating, a memo seeded from the server that has not yet
+    /// computed its own value. Reading the memo does not make it clean; it
+    /// becomes clean once it computes again. A [frozen](Memo::freeze) memo no
+    /// longer hears from its sources, so it is never dirty.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let doubled = create_memo(cx, move |_| count() * 2);
+    ///
+    /// set_count(1);
+    /// assert!(!doubled.is_dirty());
+    /// # }).dispose();
+    /// ```
+    pub fn is_dirty(&self) -> bool {
+        with_runtime(self.0.runtime, |runtime| {
+            runtime.dirty_memos.borrow().contains(&self.0.id)
+        })
+        .unwrap_or_default()
+    }
+
+    /// Whether the memo has been [frozen](Memo::freeze).
+    pub fn is_frozen(&self) -> bool {
+        with_runtime(self.0.runtime, |runtime| {
+            runtime.frozen_memos.borrow().contains(&self.0.id)
+        })
+        .unwrap_or_default()
+    }
+
+    /// Converts the memo to an `async` stream of values, which emits the first
+    /// change immediately and then waits for `duration` before emitting again.
+    ///
+    /// Any values that arrive during that cooldown are held, and the latest
+    /// one is emitted as soon as the cooldown ends, which starts a new
+    /// cooldown. If no values arrive during the cooldown, the next change is
+    /// once again emitted immediately.
+    ///
+    /// Unlike a debounced stream, this never delays the first change, which
+    /// makes it a good fit for progress indicators that should feel
+    /// responsive without updating on every change.
+    ///
+    /// On the server, the stream must be polled within a Tokio runtime. On
+    /// native platforms without an async runtime, there is no timer to wait
+    /// on, so the cooldown ends as soon as it has started.
+    pub fn to_stream_throttled(
+        &self,
+        cx: Scope,
+        duration: Duration,
+    ) -> Pin<Box<dyn futures::Stream<Item = T>>>
+    where
+        T: Clone,
+    {
+        struct Throttle<T> {
+            source: Pin<Box<dyn futures::Stream<Item = T>>>,
+            cooldown: Option<Pin<Box<dyn Future<Output = ()>>>>,
+            held: Option<T>,
+            done: bool,
+        }
+
+        let state = Throttle {
+            source: self.to_stream(cx),
+            cooldown: None,
+            held: None,
+            done: false,
+        };
+
+        Box::pin(futures::stream::unfold(
+            state,
+            move |mut state| async move {
+                use futures::{
+                    future::{select, Either},
+                    StreamExt,
+                };
+
+                loop {
+                    match state.cooldown.take() {
+                        // not cooling down: emit the next value immediately
+                        None => {
+                            if state.done {
+                                return None;
+                            }
+                            let value = state.source.next().await?;
+                            state.cooldown = Some(Box::pin(sleep(duration)));
+                            return Some((value, state));
+                        }
+                        // cooling down: hold the latest value until it ends
+                        Some(cooldown) => {
+                            if state.done {
+                                cooldown.await;
+                            } else {
+                                match select(state.source.next(), cooldown)
+                                    .await
+                                {
+                                    Either::Left((Some(value), cooldown)) => {
+                                        state.held = Some(value);
+                                        state.cooldown = Some(cooldown);
+                                        continue;
+                                    }
+                                    Either::Left((None, cooldown)) => {
+                                        state.done = true;
+                                        state.cooldown = Some(cooldown);
+                                        continue;
+                                    }
+                                    Either::Right(_) => {}
+                                }
+                            }
+                            if let Some(value) = state.held.take() {
+                                state.cooldown =
+                                    Some(Box::pin(sleep(duration)));
+                                return Some((value, state));
+                            }
+                        }
+                    }
+                }
+            },
+        ))
+    }
+
+    /// Calls `f` with the memo’s current value, and then again every time it
+    /// changes, until the returned [WatchHandle] is dropped.
+    ///
+    /// The second argument to `f` is the memo’s previous value, which will be
+    /// `None` for the initial call. Unlike effects created by
+    /// [create_effect](crate::create_effect), the watcher can be stopped
+    /// independently of its scope, and it runs on the server as well as the
+    /// client.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::{cell::RefCell, rc::Rc};
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    ///
+    /// let handle = double.watch(cx, {
+    ///     let log = Rc::clone(&log);
+    ///     move |new, old| log.borrow_mut().push((*new, old.copied()))
+    /// });
+    /// set_count(1);
+    /// assert_eq!(*log.borrow(), [(0, None), (2, Some(0))]);
+    ///
+    /// // ✅ dropping the handle stops the watcher
+    /// drop(handle);
+    /// set_count(2);
+    /// assert_eq!(log.borrow().len(), 2);
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn watch(
+        &self,
+        cx: Scope,
+        f: impl FnMut(&T, Option<&T>) + 'static,
+    ) -> WatchHandle
+    where
+        T: Clone,
+    {
+        let this = *self;
+        let f = RefCell::new(f);
+        let id = cx.runtime.create_effect(move |prev: Option<T>| {
+            let new = this.get();
+            (f.borrow_mut())(&new, prev.as_ref());
+            new
+        });
+        cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(id)));
+        WatchHandle {
+            runtime: cx.runtime,
+            id,
+        }
+    }
+
+    /// Calls `on_value` with the memo’s current value, and then again every
+    /// time it changes, until `cx` is disposed, at which point `on_drop` is
+    /// called once.
+    ///
+    /// This is a direct way to push a memo into an external system, like a
+    /// WebSocket or a canvas, with setup in the first call to `on_value` and
+    /// teardown in `on_drop`. The value is passed by reference, so it is never
+    /// cloned, and like [Memo::watch], this runs on the server as well as the
+    /// client. Once the scope is disposed, `on_value` is not called again,
+    /// even if an update was still waiting for the
+    /// [scheduler](crate::RuntimeId::set_scheduler) to flush it.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::{
+    /// #     cell::{Cell, RefCell},
+    /// #     rc::Rc,
+    /// # };
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    /// let sent = Rc::new(RefCell::new(Vec::new()));
+    /// let closed = Rc::new(Cell::new(false));
+    ///
+    /// cx.child_scope(|cx| {
+    ///     let sent = Rc::clone(&sent);
+    ///     let closed = Rc::clone(&closed);
+    ///     double.for_each(
+    ///         cx,
+    ///         move |value| sent.borrow_mut().push(*value),
+    ///         move || closed.set(true),
+    ///     );
+    ///     set_count(1);
+    /// })
+    /// .dispose();
+    /// assert!(closed.get());
+    ///
+    /// set_count(2);
+    /// assert_eq!(*sent.borrow(), [0, 2]);
+    /// # }).dispose();
+    /// ```
+    pub fn for_each(
+        &self,
+        cx: Scope,
+        on_value: impl Fn(&T) + 'static,
+        on_drop: impl FnOnce() + 'static,
+    ) {
+        let this = *self;
+        let id = cx.runtime.create_effect(move |_| this.with(&on_value));
+        cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(id)));
+        on_cleanup(cx, on_drop);
+    }
+
+    /// Returns a [Future] that resolves with the memo’s new value the next
+    /// time it changes, or with `None` if the memo’s scope or `cx` is disposed
+    /// first.
+    ///
+    /// The memo is watched from the moment this is called, not from when the
+    /// future is first polled, so a change in between is not missed. Watching
+    /// does not subscribe to the memo, and stops as soon as the future
+    /// resolves or is dropped.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    ///
+    /// let next = double.changed(cx);
+    /// set_count(1);
+    /// assert_eq!(futures::executor::block_on(next), Some(2));
+    /// # }).dispose();
+    /// ```
+    pub fn changed(&self, cx: Scope) -> impl Future<Output = Option<T>>
+    where
+        T: Clone,
+    {
+        // the sender lives in a one-shot inspector, so it is dropped, and the
+        // future resolves, once the inspector is removed for any reason
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let tx = RefCell::new(Some(tx));
+        let inspector = Rc::new(move |value: &dyn Any| {
+            if let Some(value) = value.downcast_ref::<T>() {
+                if let Some(tx) = tx.borrow_mut().take() {
+                    _ = tx.send(value.clone());
+                }
+            }
+        }) as Rc<MemoInspector>;
+        let watching = InspectorGuard {
+            runtime: self.0.runtime,
+            memo: self.0.id,
+            inspector: Rc::downgrade(&inspector),
+        };
+        _ = with_runtime(self.0.runtime, |runtime| {
+            runtime.add_memo_inspector(self.0.id, inspector)
+        });
+        on_cleanup(cx, {
+            let watching = watching.clone();
+            move || drop(watching)
+        });
+
+        async move {
+            let value = rx.await.ok();
+            drop(watching);
+            value
+        }
+    }
+
+    /// Applies `f` to the current value of the memo and subscribes the running
+    /// effect, or returns a [SignalError] describing why the value can’t be
+    /// read:
+    /// - [SignalError::RuntimeDisposed] if the memo’s runtime has been disposed;
+    /// - [SignalError::Disposed] if the scope that owns the memo has been
+    ///   disposed;
+    /// - [SignalError::Uninitialized] if the memo is in the middle of
+    ///   computing its first value, or is a memo created with
+    ///   [create_owning_memo] that has taken its previous value to compute
+    ///   the next one.
+    ///
+    /// [SignalWith::try_with] returns the same result as an [Option].
+    /// ```
+    /// # use leptos_reactive::*;
+    /// let runtime = create_runtime();
+    /// let (cx, disposer) = raw_scope_and_disposer(runtime);
+    /// let double = create_memo(cx, |_| 2 * 2);
+    /// assert_eq!(double.try_with_result(|n| *n), Ok(4));
+    ///
+    /// disposer.dispose();
+    /// assert_eq!(double.try_with_result(|n| *n), Err(SignalError::Disposed));
+    ///
+    /// runtime.dispose();
+    /// assert_eq!(
+    ///     double.try_with_result(|n| *n),
+    ///     Err(SignalError::RuntimeDisposed)
+    /// );
+    /// ```
+    #[cfg_attr(
+        debug_assertions,
+        instrument(
+            level = "trace",
+            name = "Memo::try_with_result()",
+            skip_all,
+            fields(
+                id = ?self.0.id,
+                defined_at = %self.1,
+                ty = %std::any::type_name::<T>()
+            )
+        )
+    )]
+    pub fn try_with_result<O>(
+        &self,
+        f: impl FnOnce(&T) -> O,
+    ) -> Result<O, SignalError> {
+        self.0
+            .try_with(|t| t.as_ref().map(f))?
+            .ok_or(SignalError::Uninitialized)
+    }
+
+    /// Clones the current value of the memo and subscribes the running effect,
+    /// or returns a [SignalError] describing why the value can’t be read. See
+    /// [Memo::try_with_result] for when each error is returned.
+    pub fn try_get_result(&self) -> Result<T, SignalError>
+    where
+        T: Clone,
+    {
+        self.try_with_result(T::clone)
+    }
+
+    /// Moves ownership of the memo to `new_scope`, so that it is disposed along
+    /// with `new_scope` rather than the scope that created it.
+    ///
+    /// This lets a memo created in a short-lived scope, like a child scope for
+    /// one row of a list, be kept as a cache after that scope is disposed. The
+    /// memo’s sources and subscribers are not affected.
+    ///
+    /// # Panics
+    /// Panics if `new_scope` has already been disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 1);
+    /// let mut double = None;
+    /// cx.child_scope(|child| {
+    ///     let memo = create_memo(child, move |_| count() * 2);
+    ///     memo.reparent(cx);
+    ///     double = Some(memo);
+    /// })
+    /// .dispose();
+    ///
+    /// // the memo outlived the child scope that created it
+    /// let double = double.unwrap();
+    /// set_count(2);
+    /// assert_eq!(double(), 4);
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn reparent(&self, new_scope: Scope) {
+        let moved = with_runtime(self.0.runtime, |runtime| {
+            if !runtime.scopes.borrow().contains_key(new_scope.id) {
+                return false;
+            }
+            let owner = runtime.memo_owners.borrow().get(self.0.id).copied();
+            let scopes = runtime.scopes.borrow();
+            if let Some(props) = owner.and_then(|owner| scopes.get(owner)) {
+                let effect = runtime.memo_effect(self.0.id);
+                props.borrow_mut().retain(|prop| match prop {
+                    ScopeProperty::Signal(id) => *id != self.0.id,
+                    ScopeProperty::Effect(id) => Some(*id) != effect,
+                    ScopeProperty::Resource(_) => true,
+                });
+            }
+            true
+        })
+        .unwrap_or_default();
+        assert!(
+            moved,
+            "tried to reparent a memo into a scope that has been disposed"
+        );
+        _ = self.owned_by(new_scope);
+    }
+
+    /// Registers the memo with `cx`, so that its value and its computation
+    /// are disposed along with the scope.
+    pub(crate) fn owned_by(self, cx: Scope) -> Self {
+        let effect = with_runtime(self.0.runtime, |runtime| {
+            runtime.memo_owners.borrow_mut().insert(self.0.id, cx.id);
+            runtime.memo_effect(self.0.id)
+        })
+        .ok()
+        .flatten();
+        cx.with_scope_property(|prop| {
+            prop.push(ScopeProperty::Signal(self.0.id));
+            if let Some(effect) = effect {
+                prop.push(ScopeProperty::Effect(effect));
+            }
+        });
+        self
+    }
+
+    /// The unique identifier for this memo, which can be used to
+    /// [hydrate](crate::hydrate_memo_from) it on the client.
+    pub fn id(&self) -> SignalId {
+        self.0.id
+    }
+
+    /// The node id of this memo in the reactive graph, as it appears in
+    /// [Memo::dependencies] and [Memo::subscribers] of other memos.
+    pub fn node_id(&self) -> NodeId {
+        with_runtime(self.0.runtime, |runtime| runtime.signal_node(self.0.id))
+            .expect(
+                "tried to access a memo in a runtime that had been disposed",
+            )
+    }
+
+    /// The signals and memos this memo read the last time it computed.
+    ///
+    /// This is a diagnostic tool, intended for rendering the reactive graph
+    /// while debugging, and always returns an empty `Vec` in release builds.
+    pub fn dependencies(&self) -> Vec<NodeId> {
+        #[cfg(debug_assertions)]
+        {
+            with_runtime(self.0.runtime, |runtime| {
+                runtime.memo_dependencies(self.0.id)
+            })
+            .unwrap_or_default()
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// The memos and effects that currently depend on this memo.
+    ///
+    /// Like [Memo::dependencies], this always returns an empty `Vec` in release
+    /// builds.
+    pub fn subscribers(&self) -> Vec<NodeId> {
+        #[cfg(debug_assertions)]
+        {
+            with_runtime(self.0.runtime, |runtime| {
+                runtime.memo_subscribers(self.0.id)
+            })
+            .unwrap_or_default()
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// Serializes the memo’s current value, so that it can be sent to the
+    /// client and used to [hydrate](crate::hydrate_memo_from) the memo without
+    /// running its computation again.
+    ///
+    /// This does not subscribe to the memo.
+    pub fn serialize(&self) -> Result<String, SerializationError>
+    where
+        T: Serializable,
+    {
+        self.with_untracked(Serializable::ser)
+    }
+
     #[cfg(feature = "hydrate")]
     pub(crate) fn subscribe(&self) {
         self.0.subscribe()
     }
 }
 
+/// A handle to a watcher created by [Memo::watch]. The watcher stops as soon
+/// as this handle is dropped.
+#[derive(Debug)]
+#[must_use = "the watcher stops as soon as its handle is dropped"]
+pub struct WatchHandle {
+    runtime: RuntimeId,
+    id: EffectId,
+}
+
+impl WatchHandle {
+    /// Stops watching. This is the same as dropping the handle.
+    pub fn stop(self) {}
+}
+
+/// Removes a memo inspector when dropped.
+#[derive(Clone)]
+struct InspectorGuard {
+    runtime: RuntimeId,
+    memo: SignalId,
+    inspector: Weak<MemoInspector>,
+}
+
+impl Drop for InspectorGuard {
+    fn drop(&mut self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.remove_memo_inspector(self.memo, &self.inspector)
+        });
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            self.id.cleanup(runtime);
+            runtime.effects.borrow_mut().remove(self.id);
+            runtime.effect_sources.borrow_mut().remove(self.id);
+        });
+    }
+}
+
 impl_get_fn_traits![Memo];
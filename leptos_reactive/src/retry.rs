@@ -0,0 +1,179 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_effect, create_signal, macros::debug_warn, on_cleanup,
+    spawn::set_timeout, Scope, SignalGet, SignalSet,
+};
+use cfg_if::cfg_if;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+/// Describes how often, and how quickly, an effect created with
+/// [create_effect_retry_jitter] is retried after it fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times to retry after a failure before giving up.
+    pub max_retries: usize,
+    /// The delay before the first retry. Each retry after that doubles the delay.
+    pub base_delay: Duration,
+    /// The upper limit for the delay before any one retry.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a randomized delay before the retry numbered `attempt`, counting from `0`.
+    ///
+    /// The delay is somewhere between half and all of the exponential backoff
+    /// delay for that attempt, so that many clients that failed at the same
+    /// time don’t all retry at the same time.
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let factor =
+            2u32.saturating_pow(u32::try_from(attempt).unwrap_or(u32::MAX));
+        let backoff =
+            self.base_delay.saturating_mul(factor).min(self.max_delay);
+        backoff / 2 + backoff.mul_f64(random() / 2.0)
+    }
+}
+
+/// Creates an effect that is retried with randomized exponential backoff when it fails,
+/// which is useful for effects that talk to rate-limited external resources.
+///
+/// If `f` returns an `Err`, the effect runs again after [RetryPolicy::delay], up to
+/// [RetryPolicy::max_retries] times. After that it gives up until one of its dependencies
+/// changes. Whenever the effect runs because a dependency changed, the count of retries
+/// starts over.
+///
+/// The argument to `f` is the value returned by the last successful run, which will
+/// be `None` if it has not yet succeeded.
+///
+/// On native platforms without an async runtime, where
+/// [spawn_local](crate::spawn_local) blocks until its future completes, there
+/// is no event loop to wait on, so each retry runs right away.
+///
+/// Like [create_effect], this **does not run on the server**.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc, time::Duration};
+/// # create_scope(create_runtime(), |cx| {
+/// let attempts = Rc::new(Cell::new(0));
+///
+/// create_effect_retry_jitter(
+///     cx,
+///     RetryPolicy {
+///         max_retries: 5,
+///         base_delay: Duration::from_millis(10),
+///         max_delay: Duration::from_secs(1),
+///     },
+///     {
+///         let attempts = Rc::clone(&attempts);
+///         move |_| {
+///             attempts.set(attempts.get() + 1);
+///             // pretend the first two requests are rate-limited
+///             if attempts.get() < 3 {
+///                 Err("rate limited")
+///             } else {
+///                 Ok(())
+///             }
+///         }
+///     },
+/// );
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_effect_retry_jitter<T, E>(
+    cx: Scope,
+    policy: RetryPolicy,
+    f: impl Fn(Option<&T>) -> Result<T, E> + 'static,
+) where
+    T: 'static,
+    E: 'static,
+{
+    let (retry, set_retry) = create_signal(cx, ());
+    let last = Rc::new(RefCell::new(None::<T>));
+    let attempts = Rc::new(Cell::new(0));
+    let retrying = Rc::new(Cell::new(false));
+    let disposed = Rc::new(Cell::new(false));
+
+    on_cleanup(cx, {
+        let disposed = Rc::clone(&disposed);
+        move || disposed.set(true)
+    });
+
+    create_effect(cx, move |_| {
+        retry.get();
+        if !retrying.replace(false) {
+            attempts.set(0);
+        }
+
+        let result = f(last.borrow().as_ref());
+        match result {
+            Ok(value) => *last.borrow_mut() = Some(value),
+            Err(_) => {
+                let attempt = attempts.get();
+                if attempt < policy.max_retries {
+                    attempts.set(attempt + 1);
+                    let delay = policy.delay(attempt);
+                    let retrying = Rc::clone(&retrying);
+                    let disposed = Rc::clone(&disposed);
+                    set_timeout(
+                        move || {
+                            if !disposed.get() {
+                                retrying.set(true);
+                                set_retry.set(());
+                            }
+                        },
+                        delay,
+                    );
+                } else {
+                    debug_warn!(
+                        "[create_effect_retry_jitter] giving up after {} \
+                         retries",
+                        policy.max_retries
+                    );
+                }
+            }
+        }
+    });
+}
+
+/// Returns a random number in the range `0.0..1.0`.
+fn random() -> f64 {
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            js_sys::Math::random()
+        } else {
+            use std::{
+                collections::hash_map::RandomState,
+                hash::{BuildHasher, Hasher},
+            };
+
+            // every RandomState is seeded differently, which is plenty of
+            // randomness for jitter without pulling in a dependency
+            let bits = RandomState::new().build_hasher().finish();
+            (bits >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+}
@@ -1,9 +1,12 @@
 #![forbid(unsafe_code)]
+#[cfg(feature = "catch-panics")]
+use crate::NodeId;
 use crate::{
-    hydration::SharedContext, AnyEffect, AnyResource, Effect, EffectId, Memo,
-    ReadSignal, ResourceId, ResourceState, RwSignal, Scope, ScopeDisposer,
-    ScopeId, ScopeProperty, SerializableResource, SignalId, SignalUpdate,
-    UnserializableResource, WriteSignal,
+    hydration::SharedContext, snapshot::SignalSerializer, AnyEffect,
+    AnyResource, Effect, EffectId, Memo, ReadSignal, ResourceId, ResourceState,
+    RwSignal, Scope, ScopeDisposer, ScopeId, ScopeProperty,
+    SerializableResource, SignalId, SignalUpdate, UnserializableResource,
+    WriteSignal,
 };
 use cfg_if::cfg_if;
 use futures::stream::FuturesUnordered;
@@ -16,7 +19,7 @@ use std::{
     future::Future,
     marker::PhantomData,
     pin::Pin,
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 
 pub(crate) type PinnedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
@@ -56,6 +59,33 @@ pub(crate) fn with_runtime<T>(
     }
 }
 
+/// Sets a scheduler that decides when effects run in the runtime that owns
+/// `cx`, which is useful for integrating with an external event loop. See
+/// [RuntimeId::set_scheduler] for details.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let queue = Rc::new(RefCell::new(Vec::<Box<dyn FnOnce()>>::new()));
+/// set_scheduler(cx, {
+///     let queue = Rc::clone(&queue);
+///     move |flush| queue.borrow_mut().push(flush)
+/// });
+///
+/// let (count, set_count) = create_signal(cx, 0);
+/// create_isomorphic_effect(cx, move |_| count());
+/// set_count(1);
+/// // the effect is waiting for the queued flush
+/// assert_eq!(queue.borrow().len(), 1);
+/// # }).dispose();
+/// ```
+pub fn set_scheduler(
+    cx: Scope,
+    scheduler: impl Fn(Box<dyn FnOnce()>) + 'static,
+) {
+    cx.runtime.set_scheduler(scheduler)
+}
+
 #[doc(hidden)]
 #[must_use = "Runtime will leak memory if Runtime::dispose() is never called."]
 /// Creates a new reactive [Runtime]. This should almost always be handled by the framework.
@@ -85,6 +115,97 @@ impl RuntimeId {
         }
     }
 
+    /// Sets the maximum depth of nested memo computations allowed in this runtime.
+    ///
+    /// Each memo that recomputes as a result of another memo changing adds one
+    /// level of depth. Very deep chains are usually accidental and can hurt
+    /// performance; once a limit is set, exceeding it will panic with a message
+    /// listing the memos that make up the chain.
+    ///
+    /// By default there is no limit.
+    pub fn set_max_memo_depth(self, depth: usize) {
+        _ = with_runtime(self, |runtime| {
+            runtime.max_memo_depth.set(Some(depth))
+        });
+    }
+
+    /// Returns statistics about how much work this runtime has done in
+    /// response to signal updates, which can help when profiling apps
+    /// that do a lot of reactive work.
+    ///
+    /// Each time a signal is updated, it synchronously runs all of its
+    /// subscribers; this is counted as one flush. Any updates made by those
+    /// subscribers are nested within the same flush, and add to its depth.
+    ///
+    /// Statistics are only collected in debug builds. In release builds, this
+    /// always returns [SchedulerStats::default].
+    pub fn scheduler_stats(self) -> SchedulerStats {
+        with_runtime(self, |runtime| runtime.scheduler.stats())
+            .unwrap_or_default()
+    }
+
+    /// Sets a scheduler that decides when effects run after the signals they
+    /// depend on have changed, which is useful for integrating with an external
+    /// event loop, like a game engine’s frame loop.
+    ///
+    /// By default, effects run synchronously whenever a signal changes. Once a
+    /// scheduler is set, effects are instead queued, and the scheduler is given
+    /// a function that runs every queued effect, which it can call whenever
+    /// it is ready. The scheduler is only called again once that function has
+    /// run, so several changes made in between are handled by a single flush.
+    ///
+    /// Memos are not affected: they always recompute synchronously, so reading
+    /// a memo never returns a stale value. Effects also always run immediately
+    /// when they are first created.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::{cell::{Cell, RefCell}, rc::Rc};
+    /// # let runtime = create_runtime();
+    /// # create_scope(runtime, move |cx| {
+    /// // flushes are held until the next frame
+    /// let queue = Rc::new(RefCell::new(Vec::<Box<dyn FnOnce()>>::new()));
+    /// runtime.set_scheduler({
+    ///     let queue = Rc::clone(&queue);
+    ///     move |flush| queue.borrow_mut().push(flush)
+    /// });
+    /// let tick = move || {
+    ///     let flushes = std::mem::take(&mut *queue.borrow_mut());
+    ///     for flush in flushes {
+    ///         flush();
+    ///     }
+    /// };
+    ///
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    /// let last_seen = Rc::new(Cell::new(0));
+    /// create_isomorphic_effect(cx, {
+    ///     let last_seen = Rc::clone(&last_seen);
+    ///     move |_| last_seen.set(double())
+    /// });
+    ///
+    /// set_count(1);
+    /// set_count(2);
+    /// // the memo is up to date, but the effect hasn't run yet
+    /// assert_eq!(double(), 4);
+    /// assert_eq!(last_seen.get(), 0);
+    ///
+    /// tick();
+    /// assert_eq!(last_seen.get(), 4);
+    /// # }).dispose();
+    /// ```
+    ///
+    /// Inside a [Scope], the scheduler can also be set with the
+    /// [set_scheduler](crate::set_scheduler) function.
+    pub fn set_scheduler(
+        self,
+        scheduler: impl Fn(Box<dyn FnOnce()>) + 'static,
+    ) {
+        _ = with_runtime(self, |runtime| {
+            *runtime.effect_scheduler.borrow_mut() = Some(Rc::new(scheduler))
+        });
+    }
+
     pub(crate) fn raw_scope_and_disposer(self) -> (Scope, ScopeDisposer) {
         with_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
@@ -275,23 +396,263 @@ impl RuntimeId {
     ) -> Memo<T>
     where
         T: PartialEq + Any + 'static,
+    {
+        self.create_memo_with_eq(f, |prev, new| prev == new)
+    }
+
+    /// Creates a memo that uses `is_equal` rather than [PartialEq] to decide
+    /// whether a newly-computed value should replace the previous one and
+    /// notify subscribers.
+    #[track_caller]
+    pub(crate) fn create_memo_with_eq<T>(
+        self,
+        f: impl Fn(Option<&T>) -> T + 'static,
+        is_equal: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Memo<T>
+    where
+        T: Any + 'static,
+    {
+        self.create_seeded_memo(None, f, is_equal)
+    }
+
+    /// Creates a memo like [RuntimeId::create_memo_with_eq] that, if given a
+    /// `seed`, takes it as its initial value rather than running `f`.
+    #[track_caller]
+    pub(crate) fn create_seeded_memo<T>(
+        self,
+        seed: Option<T>,
+        f: impl Fn(Option<&T>) -> T + 'static,
+        is_equal: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Memo<T>
+    where
+        T: Any + 'static,
+    {
+        self.create_memo_with_compute(seed, move |read| {
+            read.with_no_subscription(|p| {
+                let new = f(p.as_ref());
+                let changed = !matches!(p, Some(p) if is_equal(p, &new));
+                changed.then_some(new)
+            })
+        })
+    }
+
+    /// Creates a memo whose computation takes ownership of the previous value,
+    /// and returns the new value along with whether it has changed.
+    #[track_caller]
+    pub(crate) fn create_owning_memo<T>(
+        self,
+        f: impl Fn(Option<T>) -> (T, bool) + 'static,
+    ) -> Memo<T>
+    where
+        T: Any + 'static,
+    {
+        self.create_memo_with_compute(None, move |read| {
+            let prev = read
+                .id
+                .update_with_no_effect(self, |n: &mut Option<T>| n.take())
+                .flatten();
+            let (new, changed) = f(prev);
+            if changed {
+                Some(new)
+            } else {
+                read.id.update_with_no_effect(self, |n: &mut Option<T>| {
+                    *n = Some(new)
+                });
+                None
+            }
+        })
+    }
+
+    /// Passes a panic from a memo’s computation to the hook set with
+    /// [set_reactive_panic_hook](crate::set_reactive_panic_hook). Returns
+    /// `Ok(None)` if the memo can keep its previous value, or the panic to
+    /// resume otherwise.
+    #[cfg(feature = "catch-panics")]
+    fn recover_memo_panic<T>(
+        self,
+        read: ReadSignal<Option<T>>,
+        payload: Box<dyn Any + Send>,
+    ) -> Result<Option<T>, Box<dyn Any + Send>>
+    where
+        T: 'static,
+    {
+        let hook =
+            with_runtime(self, |runtime| runtime.panic_hook.borrow().clone())
+                .ok()
+                .flatten();
+        let Some(hook) = hook else {
+            return Err(payload);
+        };
+        hook(NodeId::memo(read.id), &*payload);
+        if read.with_no_subscription(Option::is_some) {
+            Ok(None)
+        } else {
+            Err(payload)
+        }
+    }
+
+    /// Creates a memo that runs `compute` whenever its dependencies change.
+    /// `compute` returns the new value, or `None` if the memo has not changed
+    /// and should not notify its subscribers.
+    ///
+    /// If the memo is given a `seed`, or was seeded from the server, it takes
+    /// that as its initial value instead of running `compute`, and computes
+    /// once [Runtime::run_seeded_memo] is called, which only happens when
+    /// hydrating.
+    #[track_caller]
+    fn create_memo_with_compute<T>(
+        self,
+        seed: Option<T>,
+        compute: impl Fn(ReadSignal<Option<T>>) -> Option<T> + 'static,
+    ) -> Memo<T>
+    where
+        T: Any + 'static,
     {
         #[cfg(debug_assertions)]
         let defined_at = std::panic::Location::caller();
 
         let (read, write) = self.create_signal(None);
+        let seed = RefCell::new(seed);
 
-        self.create_effect(move |_| {
-            let (new, changed) = read.with_no_subscription(|p| {
-                let new = f(p.as_ref());
-                let changed = Some(&new) != p.as_ref();
-                (new, changed)
-            });
+        let id = self.create_effect(move |prev: Option<()>| {
+            let mut frame = with_runtime(self, |runtime| {
+                runtime.push_memo(MemoFrame {
+                    id: read.id,
+                    #[cfg(debug_assertions)]
+                    defined_at,
+                });
+                MemoFrameGuard {
+                    runtime: self,
+                    computing: None,
+                }
+            })
+            .ok();
 
-            if changed {
-                write.update(|n| *n = Some(new));
+            // a seeded memo skips its first computation, and is re-run by
+            // `run_seeded_memo` to discover its dependencies
+            if prev.is_none() {
+                let seed = seed.borrow_mut().take();
+                #[cfg(feature = "hydrate")]
+                let seed = seed.or_else(|| {
+                    let seed = with_runtime(self, |runtime| {
+                        runtime
+                            .memo_seeds
+                            .borrow_mut()
+                            .remove(&read.id)
+                            .and_then(|seed| seed.downcast::<T>().ok())
+                    })
+                    .ok()
+                    .flatten()
+                    .map(|seed| *seed);
+                    // a memo seeded from the server catches up with the
+                    // client’s signals once the hydration pass has finished
+                    #[cfg(target_arch = "wasm32")]
+                    if seed.is_some() {
+                        crate::queue_microtask(move || {
+                            _ = with_runtime(self, |runtime| {
+                                runtime.run_seeded_memo(read.id, self)
+                            });
+                        });
+                    }
+                    seed
+                });
+                if let Some(seed) = seed {
+                    read.id.update_with_no_effect(self, |n: &mut Option<T>| {
+                        *n = Some(seed)
+                    });
+                    #[cfg(feature = "hydrate")]
+                    {
+                        _ = with_runtime(self, |runtime| {
+                            if let Some(id) = runtime.observer.get() {
+                                runtime.seeded_memos.borrow_mut().push(id);
+                            }
+                            runtime.mark_memo_dirty(read.id);
+                        });
+                    }
+                    return;
+                }
+            }
+
+            // cleanups registered by the previous computation run before
+            // the next one, and anything registered while computing is
+            // attached to this memo rather than to its scope
+            let computing = with_runtime(self, |runtime| {
+                runtime.run_memo_cleanups(read.id, false);
+                runtime.dirty_memos.borrow_mut().remove(&read.id);
+                runtime.computing_memo.replace(Some(read.id))
+            })
+            .ok()
+            .flatten();
+            if let Some(frame) = &mut frame {
+                frame.computing = Some(computing);
+            }
+
+            #[cfg(not(feature = "catch-panics"))]
+            let new = compute(read);
+            #[cfg(feature = "catch-panics")]
+            let new =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    compute(read)
+                }))
+                .or_else(|payload| self.recover_memo_panic(read, payload));
+
+            if let Some(frame) = &mut frame {
+                frame.end_computing();
+            }
+
+            #[cfg(feature = "catch-panics")]
+            let new = match new {
+                Ok(new) => new,
+                Err(payload) => std::panic::resume_unwind(payload),
+            };
+
+            if let Some(new) = new {
+                // the initial value is version 0
+                if prev.is_some() {
+                    _ = with_runtime(self, |runtime| {
+                        let mut versions = runtime.memo_versions.borrow_mut();
+                        match versions.get_mut(read.id) {
+                            Some(version) => *version += 1,
+                            None => {
+                                versions.insert(read.id, 1);
+                            }
+                        }
+                    });
+                }
+
+                let prev = read
+                    .id
+                    .update_with_no_effect(self, |n: &mut Option<T>| {
+                        n.replace(new)
+                    })
+                    .flatten();
+                let inspectors = with_runtime(self, |runtime| {
+                    runtime.keep_previous_memo_value(read.id, prev);
+                    runtime.memo_inspectors.borrow().get(read.id).cloned()
+                })
+                .ok()
+                .flatten();
+                // inspectors see the new value before dependents do
+                if let Some(inspectors) = inspectors {
+                    read.with_no_subscription(|n| {
+                        if let Some(n) = n {
+                            for inspector in &inspectors {
+                                inspector(n);
+                            }
+                        }
+                    });
+                }
+                write.update(|_| {});
             }
         });
+        _ = with_runtime(self, |runtime| {
+            runtime.memo_effects.borrow_mut().insert(id, read.id);
+            runtime.memo_effect_ids.borrow_mut().insert(read.id, id);
+            // there is no hydration pass to wait for outside the browser, so
+            // a memo seeded from the server catches up with the client at once
+            #[cfg(all(feature = "hydrate", not(target_arch = "wasm32")))]
+            runtime.run_seeded_memo(read.id, self);
+        });
 
         Memo(
             read,
@@ -312,6 +673,9 @@ pub(crate) struct Runtime {
     pub scope_contexts:
         RefCell<SparseSecondaryMap<ScopeId, HashMap<TypeId, Box<dyn Any>>>>,
     #[allow(clippy::type_complexity)]
+    pub scope_shared_memos:
+        RefCell<SparseSecondaryMap<ScopeId, HashMap<TypeId, Box<dyn Any>>>>,
+    #[allow(clippy::type_complexity)]
     pub scope_cleanups:
         RefCell<SparseSecondaryMap<ScopeId, Vec<Box<dyn FnOnce()>>>>,
     pub signals: RefCell<SlotMap<SignalId, Rc<RefCell<dyn Any>>>>,
@@ -320,7 +684,180 @@ pub(crate) struct Runtime {
     pub effects: RefCell<SlotMap<EffectId, Rc<dyn AnyEffect>>>,
     pub effect_sources:
         RefCell<SecondaryMap<EffectId, RefCell<HashSet<SignalId>>>>,
+    pub effect_scopes: RefCell<SecondaryMap<EffectId, ScopeId>>,
     pub resources: RefCell<SlotMap<ResourceId, AnyResource>>,
+    pub memo_stack: RefCell<Vec<MemoFrame>>,
+    pub max_memo_depth: Cell<Option<usize>>,
+    pub scheduler: SchedulerCounters,
+    pub computing_memo: Cell<Option<SignalId>>,
+    pub memo_effects: RefCell<HashMap<EffectId, SignalId>>,
+    pub memo_effect_ids: RefCell<SecondaryMap<SignalId, EffectId>>,
+    pub memo_owners: RefCell<SecondaryMap<SignalId, ScopeId>>,
+    pub frozen_memos: RefCell<HashSet<SignalId>>,
+    pub dirty_memos: RefCell<HashSet<SignalId>>,
+    pub memo_versions: RefCell<SecondaryMap<SignalId, u64>>,
+    pub memo_previous: RefCell<SecondaryMap<SignalId, Option<Rc<dyn Any>>>>,
+    pub memo_inspectors:
+        RefCell<SecondaryMap<SignalId, Vec<Rc<MemoInspector>>>>,
+    #[cfg(debug_assertions)]
+    #[allow(clippy::type_complexity)]
+    pub value_formatters:
+        RefCell<SecondaryMap<SignalId, Rc<dyn Fn(&dyn Any) -> String>>>,
+    #[cfg(feature = "catch-panics")]
+    pub panic_hook: RefCell<Option<Rc<PanicHook>>>,
+    #[allow(clippy::type_complexity)]
+    pub effect_scheduler: RefCell<Option<Rc<dyn Fn(Box<dyn FnOnce()>)>>>,
+    pub pending_effects: RefCell<Vec<EffectId>>,
+    pub held_effects: RefCell<Option<Vec<EffectId>>>,
+    pub paused_scopes: RefCell<SparseSecondaryMap<ScopeId, usize>>,
+    pub paused_effects: RefCell<Vec<EffectId>>,
+    pub signal_serializers: RefCell<SecondaryMap<SignalId, SignalSerializer>>,
+    #[allow(clippy::type_complexity)]
+    pub memo_cleanups: RefCell<HashMap<SignalId, Vec<Box<dyn FnOnce()>>>>,
+    #[cfg(feature = "hydrate")]
+    pub memo_seeds: RefCell<HashMap<SignalId, Box<dyn Any>>>,
+    #[cfg(feature = "hydrate")]
+    pub seeded_memos: RefCell<Vec<EffectId>>,
+}
+
+pub(crate) type MemoInspector = dyn Fn(&dyn Any);
+
+#[cfg(feature = "catch-panics")]
+pub(crate) type PanicHook = dyn Fn(NodeId, &(dyn Any + Send));
+
+/// Statistics about the work done by a runtime in response to signal updates.
+/// See [RuntimeId::scheduler_stats].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SchedulerStats {
+    /// The number of top-level flushes, i.e., signal updates that ran at
+    /// least one subscriber and were not made while another update was
+    /// already notifying its subscribers.
+    pub flushes: usize,
+    /// The average number of effects and memos run during each flush.
+    pub average_nodes_per_flush: f64,
+    /// The deepest level of nested updates seen within a single flush.
+    pub max_flush_depth: usize,
+}
+
+#[derive(Default)]
+pub(crate) struct SchedulerCounters {
+    #[cfg(debug_assertions)]
+    flushes: Cell<usize>,
+    #[cfg(debug_assertions)]
+    nodes: Cell<usize>,
+    #[cfg(debug_assertions)]
+    nodes_in_flush: Cell<usize>,
+    #[cfg(debug_assertions)]
+    max_depth: Cell<usize>,
+    depth: Cell<usize>,
+    #[allow(clippy::type_complexity)]
+    after_flush: RefCell<Vec<Box<dyn FnOnce()>>>,
+}
+
+impl SchedulerCounters {
+    pub(crate) fn enter_flush(&self) {
+        self.depth.set(self.depth.get() + 1);
+    }
+
+    pub(crate) fn exit_flush(&self) {
+        let depth = self.depth.get().saturating_sub(1);
+        self.depth.set(depth);
+        if depth == 0 {
+            #[cfg(debug_assertions)]
+            {
+                let nodes = self.nodes_in_flush.take();
+                if nodes > 0 {
+                    self.flushes.set(self.flushes.get() + 1);
+                    self.nodes.set(self.nodes.get() + nodes);
+                }
+            }
+
+            let callbacks = self.after_flush.take();
+            for callback in callbacks {
+                callback();
+            }
+        }
+    }
+
+    /// Runs `f` once the current flush has finished notifying subscribers,
+    /// or immediately if there is no flush in progress.
+    pub(crate) fn after_flush(&self, f: impl FnOnce() + 'static) {
+        if self.depth.get() == 0 {
+            f();
+        } else {
+            self.after_flush.borrow_mut().push(Box::new(f));
+        }
+    }
+
+    pub(crate) fn record_node(&self) {
+        #[cfg(debug_assertions)]
+        {
+            self.nodes_in_flush.set(self.nodes_in_flush.get() + 1);
+            self.max_depth
+                .set(self.max_depth.get().max(self.depth.get()));
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn stats(&self) -> SchedulerStats {
+        SchedulerStats::default()
+    }
+
+    #[cfg(debug_assertions)]
+    fn stats(&self) -> SchedulerStats {
+        let flushes = self.flushes.get();
+        SchedulerStats {
+            flushes,
+            average_nodes_per_flush: if flushes == 0 {
+                0.0
+            } else {
+                self.nodes.get() as f64 / flushes as f64
+            },
+            max_flush_depth: self.max_depth.get(),
+        }
+    }
+}
+
+/// A memo that is currently computing, or notifying its dependents.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MemoFrame {
+    pub id: SignalId,
+    #[cfg(debug_assertions)]
+    pub defined_at: &'static std::panic::Location<'static>,
+}
+
+impl std::fmt::Display for MemoFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Memo({:?})", self.id)?;
+        #[cfg(debug_assertions)]
+        write!(f, " defined at {}", self.defined_at)?;
+        Ok(())
+    }
+}
+
+/// Pops a memo’s frame off the memo stack when dropped, and restores the memo
+/// that was computing before it, so that both stay balanced even if the
+/// memo’s computation panics.
+struct MemoFrameGuard {
+    runtime: RuntimeId,
+    computing: Option<Option<SignalId>>,
+}
+
+impl MemoFrameGuard {
+    fn end_computing(&mut self) {
+        if let Some(computing) = self.computing.take() {
+            _ = with_runtime(self.runtime, |runtime| {
+                runtime.computing_memo.set(computing)
+            });
+        }
+    }
+}
+
+impl Drop for MemoFrameGuard {
+    fn drop(&mut self) {
+        self.end_computing();
+        _ = with_runtime(self.runtime, |runtime| runtime.pop_memo());
+    }
 }
 
 impl Debug for Runtime {
@@ -344,6 +881,255 @@ impl Runtime {
         Self::default()
     }
 
+    pub(crate) fn push_memo(&self, frame: MemoFrame) {
+        let mut stack = self.memo_stack.borrow_mut();
+
+        // a memo that is re-entered while it is still computing or notifying
+        // its dependents is part of a cycle, which would otherwise end in a
+        // stack overflow or a confusing `BorrowMutError`
+        #[cfg(debug_assertions)]
+        if let Some(start) = stack.iter().position(|f| f.id == frame.id) {
+            let cycle = stack[start..]
+                .iter()
+                .chain(std::iter::once(&frame))
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n  -> ");
+            drop(stack);
+            panic!("reactive cycle detected:\n  {cycle}");
+        }
+
+        stack.push(frame);
+        if let Some(max) = self.max_memo_depth.get() {
+            if stack.len() > max {
+                let chain = stack
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n  -> ");
+                stack.pop();
+                drop(stack);
+                panic!(
+                    "memo dependency chain exceeded the maximum memo depth of \
+                     {max}:\n  {chain}"
+                );
+            }
+        }
+    }
+
+    pub(crate) fn pop_memo(&self) {
+        self.memo_stack.borrow_mut().pop();
+    }
+
+    /// If the given effect is paused, or a scheduler has been set, queues the
+    /// effect to run later rather than immediately, and returns `true`.
+    pub(crate) fn defer_effect(
+        &self,
+        id: EffectId,
+        runtime_id: RuntimeId,
+    ) -> bool {
+        if self.memo_effects.borrow().contains_key(&id) {
+            return false;
+        }
+        if let Some(held) = &mut *self.held_effects.borrow_mut() {
+            if !held.contains(&id) {
+                held.push(id);
+            }
+            return true;
+        }
+        if self.is_effect_paused(id) {
+            let mut paused = self.paused_effects.borrow_mut();
+            if !paused.contains(&id) {
+                paused.push(id);
+            }
+            return true;
+        }
+
+        let scheduler = self.effect_scheduler.borrow().clone();
+        let Some(scheduler) = scheduler else {
+            return false;
+        };
+
+        let mut pending = self.pending_effects.borrow_mut();
+        let needs_flush = pending.is_empty();
+        if !pending.contains(&id) {
+            pending.push(id);
+        }
+        drop(pending);
+
+        if needs_flush {
+            scheduler(Box::new(move || {
+                _ = with_runtime(runtime_id, |runtime| {
+                    let pending = std::mem::take(
+                        &mut *runtime.pending_effects.borrow_mut(),
+                    );
+                    runtime.scheduler.enter_flush();
+                    for id in pending {
+                        let effect = runtime.effects.borrow().get(id).cloned();
+                        if let Some(effect) = effect {
+                            runtime.scheduler.record_node();
+                            effect.run(id, runtime_id);
+                        }
+                    }
+                    runtime.scheduler.exit_flush();
+                });
+            }));
+        }
+        true
+    }
+
+    /// The effects subscribed to the signal with the given ID.
+    pub(crate) fn subscribers(&self, id: SignalId) -> HashSet<EffectId> {
+        self.signal_subscribers
+            .borrow()
+            .get(id)
+            .map(|subs| subs.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    /// Runs the effects subscribed to the signal with the given ID, other than
+    /// those in `except`, as one flush.
+    pub(crate) fn notify_subscribers(
+        &self,
+        id: SignalId,
+        runtime_id: RuntimeId,
+        except: &HashSet<EffectId>,
+    ) {
+        self.scheduler.enter_flush();
+        for sub in self.subscribers(id) {
+            if except.contains(&sub) {
+                continue;
+            }
+            let effect = self.effects.borrow().get(sub).cloned();
+            if let Some(effect) = effect {
+                if self.defer_effect(sub, runtime_id) {
+                    continue;
+                }
+                self.scheduler.record_node();
+                effect.run(sub, runtime_id);
+            }
+        }
+        self.scheduler.exit_flush();
+    }
+
+    /// Makes the memo with the given ID keep the value it replaces each time it
+    /// changes, rather than dropping it.
+    pub(crate) fn retain_previous_memo_value(&self, memo: SignalId) {
+        let mut previous = self.memo_previous.borrow_mut();
+        if !previous.contains_key(memo) {
+            previous.insert(memo, None);
+        }
+    }
+
+    /// Keeps `prev`, the value a memo has just replaced, if the memo has been
+    /// asked to with [Runtime::retain_previous_memo_value].
+    fn keep_previous_memo_value<T: 'static>(
+        &self,
+        memo: SignalId,
+        prev: Option<T>,
+    ) {
+        if let Some(kept) = self.memo_previous.borrow_mut().get_mut(memo) {
+            *kept = prev.map(|prev| Rc::new(prev) as Rc<dyn Any>);
+        }
+    }
+
+    /// Calls `inspector` with each new value of the memo with the given ID,
+    /// just before its dependents are notified.
+    pub(crate) fn add_memo_inspector(
+        &self,
+        memo: SignalId,
+        inspector: Rc<MemoInspector>,
+    ) {
+        let mut inspectors = self.memo_inspectors.borrow_mut();
+        match inspectors.get_mut(memo) {
+            Some(inspectors) => inspectors.push(inspector),
+            None => {
+                inspectors.insert(memo, vec![inspector]);
+            }
+        }
+    }
+
+    /// Removes an inspector added with [Runtime::add_memo_inspector].
+    pub(crate) fn remove_memo_inspector(
+        &self,
+        memo: SignalId,
+        inspector: &Weak<MemoInspector>,
+    ) {
+        if let Some(inspectors) =
+            self.memo_inspectors.borrow_mut().get_mut(memo)
+        {
+            inspectors.retain(|i| !Weak::ptr_eq(&Rc::downgrade(i), inspector));
+        }
+    }
+
+    /// The effect that computes the memo with the given ID.
+    pub(crate) fn memo_effect(&self, memo: SignalId) -> Option<EffectId> {
+        self.memo_effect_ids.borrow().get(memo).copied()
+    }
+
+    /// Marks the memo with the given ID as [dirty](crate::Memo::is_dirty)
+    /// until it next computes.
+    pub(crate) fn mark_memo_dirty(&self, memo: SignalId) {
+        self.dirty_memos.borrow_mut().insert(memo);
+    }
+
+    /// Computes a seeded memo that has not run yet, so that it discovers its
+    /// dependencies.
+    #[cfg(feature = "hydrate")]
+    pub(crate) fn run_seeded_memo(
+        &self,
+        memo: SignalId,
+        runtime_id: RuntimeId,
+    ) {
+        let Some(effect) = self.memo_effect(memo) else {
+            return;
+        };
+        let was_seeded = {
+            let mut seeded = self.seeded_memos.borrow_mut();
+            let len = seeded.len();
+            seeded.retain(|id| *id != effect);
+            seeded.len() != len
+        };
+        if was_seeded {
+            effect.run(runtime_id);
+        }
+    }
+
+    /// Whether the given effect is owned by a scope that is paused, or by one
+    /// of its descendants.
+    pub(crate) fn is_effect_paused(&self, id: EffectId) -> bool {
+        let paused = self.paused_scopes.borrow();
+        if paused.is_empty() {
+            return false;
+        }
+        let parents = self.scope_parents.borrow();
+        let mut scope = self.effect_scopes.borrow().get(id).copied();
+        while let Some(id) = scope {
+            if paused.contains_key(id) {
+                return true;
+            }
+            scope = parents.get(id).copied();
+        }
+        false
+    }
+
+    /// Runs the cleanups registered by the last computation of the memo
+    /// with the given ID. If `dispose` is `true`, the memo will no longer
+    /// track cleanups.
+    pub(crate) fn run_memo_cleanups(&self, id: SignalId, dispose: bool) {
+        let cleanups = {
+            let mut memo_cleanups = self.memo_cleanups.borrow_mut();
+            if dispose {
+                memo_cleanups.remove(&id)
+            } else {
+                memo_cleanups.get_mut(&id).map(std::mem::take)
+            }
+        };
+        for cleanup in cleanups.into_iter().flatten() {
+            cleanup();
+        }
+    }
+
     pub(crate) fn create_unserializable_resource(
         &self,
         state: Rc<dyn UnserializableResource>,
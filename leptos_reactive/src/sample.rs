@@ -0,0 +1,63 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_isomorphic_effect, create_signal, Scope, Signal, SignalGetUntracked,
+    SignalSet, SignalWith,
+};
+
+/// Creates a signal that holds the value of `value` as it was the last time
+/// `trigger` notified its subscribers.
+///
+/// Changes to `value` between triggers are ignored; only the value at the
+/// moment the trigger fires is captured. This is similar to the `sample`
+/// operator in Rx-style libraries. Until the trigger fires for the first time,
+/// the signal holds the value `value` had when it was created.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (value, set_value) = create_signal(cx, 0);
+/// let (trigger, fire) = create_signal(cx, ());
+/// let sampled = sample_on(cx, value.into(), trigger.into());
+///
+/// set_value(1);
+/// set_value(2);
+/// set_value(3);
+/// // ❌ intermediate changes are ignored...
+/// assert_eq!(sampled(), 0);
+///
+/// // ✅ ...until the trigger fires
+/// fire(());
+/// assert_eq!(sampled(), 3);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn sample_on<T>(
+    cx: Scope,
+    value: Signal<T>,
+    trigger: Signal<()>,
+) -> Signal<T>
+where
+    T: Clone + 'static,
+{
+    let (sampled, set_sampled) = create_signal(cx, value.get_untracked());
+
+    create_isomorphic_effect(cx, move |prev: Option<()>| {
+        trigger.with(|_| {});
+        if prev.is_some() {
+            set_sampled.set(value.get_untracked());
+        }
+    });
+
+    sampled.into()
+}
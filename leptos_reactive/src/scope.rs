@@ -181,6 +181,98 @@ impl Scope {
              disposed",
         )
     }
+
+    /// Pauses all effects owned by this scope and its child scopes.
+    ///
+    /// While paused, signal updates are still applied and memos still
+    /// recompute, so reading values (including with `get_untracked`) stays
+    /// accurate, but effects that would have run are queued instead. When the
+    /// scope is resumed with [Scope::resume_effects], each queued effect runs
+    /// once, no matter how many times it was notified.
+    ///
+    /// Pausing can be nested: effects only resume once `resume_effects` has
+    /// been called as many times as `pause_effects`. An effect created while
+    /// its scope is paused still runs once immediately, to discover its
+    /// dependencies, and is paused from then on.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::{cell::Cell, rc::Rc};
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let runs = Rc::new(Cell::new(0));
+    /// create_isomorphic_effect(cx, {
+    ///     let runs = Rc::clone(&runs);
+    ///     move |_| {
+    ///         count();
+    ///         runs.set(runs.get() + 1);
+    ///     }
+    /// });
+    /// assert_eq!(runs.get(), 1);
+    ///
+    /// cx.pause_effects();
+    /// set_count(1);
+    /// set_count(2);
+    /// assert_eq!(count.get_untracked(), 2);
+    /// assert_eq!(runs.get(), 1);
+    ///
+    /// cx.resume_effects();
+    /// assert_eq!(runs.get(), 2);
+    /// # }).dispose();
+    /// ```
+    pub fn pause_effects(&self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            let mut paused = runtime.paused_scopes.borrow_mut();
+            match paused.get_mut(self.id) {
+                Some(count) => *count += 1,
+                None => {
+                    paused.insert(self.id, 1);
+                }
+            }
+        });
+    }
+
+    /// Resumes effects paused with [Scope::pause_effects], running every
+    /// effect that was notified while paused.
+    ///
+    /// Calling this on a scope that is not paused does nothing.
+    pub fn resume_effects(&self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            {
+                let mut paused = runtime.paused_scopes.borrow_mut();
+                match paused.get_mut(self.id) {
+                    Some(count) if *count > 1 => {
+                        *count -= 1;
+                        return;
+                    }
+                    Some(_) => {
+                        paused.remove(self.id);
+                    }
+                    None => return,
+                }
+            }
+
+            // effects might still be paused by another scope
+            let queued =
+                std::mem::take(&mut *runtime.paused_effects.borrow_mut());
+            let (paused, ready): (Vec<_>, Vec<_>) = queued
+                .into_iter()
+                .partition(|id| runtime.is_effect_paused(*id));
+            *runtime.paused_effects.borrow_mut() = paused;
+
+            runtime.scheduler.enter_flush();
+            for id in ready {
+                let effect = runtime.effects.borrow().get(id).cloned();
+                if let Some(effect) = effect {
+                    if runtime.defer_effect(id, self.runtime) {
+                        continue;
+                    }
+                    runtime.scheduler.record_node();
+                    effect.run(id, self.runtime);
+                }
+            }
+            runtime.scheduler.exit_flush();
+        });
+    }
 }
 
 // Internals
@@ -209,6 +301,8 @@ impl Scope {
                     .dispose();
                 }
             }
+            runtime.paused_scopes.borrow_mut().remove(self.id);
+
             // run cleanups
             if let Some(cleanups) =
                 runtime.scope_cleanups.borrow_mut().remove(self.id)
@@ -218,6 +312,8 @@ impl Scope {
                 }
             }
 
+            runtime.scope_shared_memos.borrow_mut().remove(self.id);
+
             // remove everything we own and run cleanups
             let owned = {
                 let owned = runtime.scopes.borrow_mut().remove(self.id);
@@ -229,6 +325,15 @@ impl Scope {
                         ScopeProperty::Signal(id) => {
                             // remove the signal
                             runtime.signals.borrow_mut().remove(id);
+                            runtime.signal_serializers.borrow_mut().remove(id);
+                            runtime.memo_owners.borrow_mut().remove(id);
+                            runtime.memo_versions.borrow_mut().remove(id);
+                            runtime.memo_previous.borrow_mut().remove(id);
+                            runtime.memo_inspectors.borrow_mut().remove(id);
+                            runtime.frozen_memos.borrow_mut().remove(&id);
+                            runtime.dirty_memos.borrow_mut().remove(&id);
+                            #[cfg(debug_assertions)]
+                            runtime.value_formatters.borrow_mut().remove(id);
                             let subs = runtime
                                 .signal_subscribers
                                 .borrow_mut()
@@ -251,6 +356,15 @@ impl Scope {
                         ScopeProperty::Effect(id) => {
                             runtime.effects.borrow_mut().remove(id);
                             runtime.effect_sources.borrow_mut().remove(id);
+                            runtime.effect_scopes.borrow_mut().remove(id);
+                            if let Some(memo) =
+                                runtime.memo_effects.borrow_mut().remove(&id)
+                            {
+                                runtime
+                                    .memo_effect_ids
+                                    .borrow_mut()
+                                    .remove(memo);
+                            }
                         }
                         ScopeProperty::Resource(id) => {
                             runtime.resources.borrow_mut().remove(id);
@@ -268,7 +382,18 @@ impl Scope {
         _ = with_runtime(self.runtime, |runtime| {
             let scopes = runtime.scopes.borrow();
             if let Some(scope) = scopes.get(self.id) {
-                f(&mut scope.borrow_mut());
+                let mut props = scope.borrow_mut();
+                let len = props.len();
+                f(&mut props);
+
+                // remember which scope owns each new effect, so that pausing
+                // a scope can tell whether an effect belongs to it
+                let mut owners = runtime.effect_scopes.borrow_mut();
+                for prop in props.get(len..).unwrap_or_default() {
+                    if let ScopeProperty::Effect(id) = prop {
+                        owners.insert(*id, self.id);
+                    }
+                }
             } else {
                 console_warn(
                     "tried to add property to a scope that has been disposed",
@@ -295,14 +420,67 @@ impl Scope {
 ///
 /// It runs after child scopes have been disposed, but before signals, effects, and resources
 /// are invalidated.
+///
+/// If it is called while a [Memo](crate::Memo) is computing its value, the cleanup
+/// function is instead tied to that computation: it runs when the memo’s dependencies
+/// change, right before the memo computes its next value, or when the scope is disposed,
+/// whichever comes first. Cleanups run in the order they were registered.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let (config, set_config) = create_signal(cx, "a");
+///
+/// create_memo(cx, {
+///     let log = Rc::clone(&log);
+///     move |_| {
+///         let config = config();
+///         log.borrow_mut().push(format!("open {config}"));
+///         on_cleanup(cx, {
+///             let log = Rc::clone(&log);
+///             move || log.borrow_mut().push(format!("close {config}"))
+///         });
+///         config
+///     }
+/// });
+///
+/// set_config("b");
+/// assert_eq!(*log.borrow(), ["open a", "close a", "open b"]);
+/// # }).dispose();
+/// ```
 pub fn on_cleanup(cx: Scope, cleanup_fn: impl FnOnce() + 'static) {
     _ = with_runtime(cx.runtime, |runtime| {
+        let cleanup_fn: Box<dyn FnOnce()> = match runtime.computing_memo.get() {
+            Some(memo) => {
+                let mut memo_cleanups = runtime.memo_cleanups.borrow_mut();
+                let registered = memo_cleanups.contains_key(&memo);
+                memo_cleanups
+                    .entry(memo)
+                    .or_default()
+                    .push(Box::new(cleanup_fn));
+                if registered {
+                    return;
+                }
+
+                // the first time, make sure the memo's cleanups also run
+                // when the scope is disposed
+                let runtime = cx.runtime;
+                Box::new(move || {
+                    _ = with_runtime(runtime, |runtime| {
+                        runtime.run_memo_cleanups(memo, true)
+                    });
+                })
+            }
+            None => Box::new(cleanup_fn),
+        };
+
         let mut cleanups = runtime.scope_cleanups.borrow_mut();
         let cleanups = cleanups
             .entry(cx.id)
             .expect("trying to clean up a Scope that has already been disposed")
             .or_insert_with(Default::default);
-        cleanups.push(Box::new(cleanup_fn));
+        cleanups.push(cleanup_fn);
     })
 }
 
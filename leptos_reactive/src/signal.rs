@@ -4,11 +4,11 @@ use crate::{
     macros::debug_warn,
     on_cleanup,
     runtime::{with_runtime, RuntimeId},
-    Runtime, Scope, ScopeProperty,
+    NodeId, Runtime, Scope, ScopeProperty,
 };
 use cfg_if::cfg_if;
 use futures::Stream;
-use std::{fmt::Debug, marker::PhantomData, pin::Pin};
+use std::{collections::HashSet, fmt::Debug, marker::PhantomData, pin::Pin};
 use thiserror::Error;
 
 macro_rules! impl_get_fn_traits {
@@ -268,6 +268,117 @@ pub trait SignalStream<T> {
     fn to_stream(&self, cx: Scope) -> Pin<Box<dyn Stream<Item = T>>>;
 }
 
+/// Extends [`SignalStream`] with streams that transform the signal’s values.
+///
+/// Unlike calling [`StreamExt`](futures::StreamExt) methods on the result of
+/// [`to_stream`](SignalStream::to_stream), these run the transformation
+/// inside the effect that drives the stream, so only transformed values are
+/// sent over the channel, and the transformation is dropped along with the
+/// effect when the [Scope] is disposed.
+///
+/// Like [`to_stream`](SignalStream::to_stream), the streams emit the current
+/// value immediately, and do not emit anything on the server.
+/// ```
+/// # use leptos_reactive::*;
+/// # use futures::StreamExt;
+/// # create_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 0);
+/// let evens = count.filter_stream(cx, |n| n % 2 == 0);
+///
+/// set_count(1);
+/// set_count(2);
+/// # if !cfg!(feature = "ssr") {
+/// let evens = futures::executor::block_on(evens.take(2).collect::<Vec<_>>());
+/// assert_eq!(evens, [0, 2]);
+/// # }
+/// # }).dispose();
+/// ```
+pub trait SignalStreamExt<T>: SignalWith<T> + Clone + 'static {
+    /// Generates a [`Stream`] that emits `f` applied to the signal’s value,
+    /// whenever the signal changes.
+    #[track_caller]
+    fn map_stream<U: 'static>(
+        &self,
+        cx: Scope,
+        f: impl Fn(&T) -> U + 'static,
+    ) -> Pin<Box<dyn Stream<Item = U>>> {
+        let this = self.clone();
+        stream_from_effect(cx, move |tx, _: Option<()>| {
+            let _ = tx.unbounded_send(this.with(&f));
+        })
+    }
+
+    /// Generates a [`Stream`] that emits the signal’s new value whenever it
+    /// changes, if the value passes the `predicate`.
+    #[track_caller]
+    fn filter_stream(
+        &self,
+        cx: Scope,
+        predicate: impl Fn(&T) -> bool + 'static,
+    ) -> Pin<Box<dyn Stream<Item = T>>>
+    where
+        T: Clone + 'static,
+    {
+        let this = self.clone();
+        stream_from_effect(cx, move |tx, _: Option<()>| {
+            if let Some(value) =
+                this.with(|value| predicate(value).then(|| value.clone()))
+            {
+                let _ = tx.unbounded_send(value);
+            }
+        })
+    }
+
+    /// Generates a [`Stream`] of accumulated values. Whenever the signal
+    /// changes, `f` is called with the previous accumulator (starting with
+    /// `initial`) and the signal’s value, and the result is emitted.
+    #[track_caller]
+    fn scan_stream<A>(
+        &self,
+        cx: Scope,
+        initial: A,
+        f: impl Fn(A, &T) -> A + 'static,
+    ) -> Pin<Box<dyn Stream<Item = A>>>
+    where
+        A: Clone + 'static,
+    {
+        let this = self.clone();
+        let initial = std::cell::Cell::new(Some(initial));
+        stream_from_effect(cx, move |tx, acc: Option<A>| {
+            let acc = acc
+                .or_else(|| initial.take())
+                .expect("the accumulator is always set after the first value");
+            let acc = this.with(|value| f(acc, value));
+            let _ = tx.unbounded_send(acc.clone());
+            acc
+        })
+    }
+}
+
+impl<T, S> SignalStreamExt<T> for S where S: SignalWith<T> + Clone + 'static {}
+
+/// Creates a stream whose values are sent by an effect that runs `f`.
+#[track_caller]
+fn stream_from_effect<U, V>(
+    cx: Scope,
+    f: impl Fn(&futures::channel::mpsc::UnboundedSender<U>, Option<V>) -> V
+        + 'static,
+) -> Pin<Box<dyn Stream<Item = U>>>
+where
+    U: 'static,
+    V: 'static,
+{
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    let close_channel = tx.clone();
+
+    on_cleanup(cx, move || close_channel.close_channel());
+
+    create_effect(cx, move |prev| f(&tx, prev));
+
+    Box::pin(rx)
+}
+
 /// Creates a signal, the basic reactive primitive.
 ///
 /// A signal is a piece of data that may change over time,
@@ -723,6 +834,15 @@ impl<T> ReadSignal<T>
 where
     T: 'static,
 {
+    /// The node id of this signal in the reactive graph, which can be passed to
+    /// [Scope::lookup_value](crate::Scope::lookup_value) in debug builds.
+    pub fn node_id(&self) -> NodeId {
+        with_runtime(self.runtime, |runtime| runtime.signal_node(self.id))
+            .expect(
+                "tried to access a signal in a runtime that had been disposed",
+            )
+    }
+
     pub(crate) fn with_no_subscription<U>(&self, f: impl FnOnce(&T) -> U) -> U {
         self.id.with_no_subscription(self.runtime, f)
     }
@@ -747,6 +867,22 @@ where
     }
 }
 
+impl<T> ReadSignal<T>
+where
+    T: Debug + 'static,
+{
+    /// Marks the signal as debuggable, so that
+    /// [Scope::lookup_value](crate::Scope::lookup_value) prints its value
+    /// rather than `"<opaque>"`. This does nothing in release builds.
+    pub fn debuggable(self) -> Self {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime
+                .set_value_formatter(self.id, |value: &T| format!("{value:?}"))
+        });
+        self
+    }
+}
+
 impl<T> Clone for ReadSignal<T> {
     fn clone(&self) -> Self {
         Self {
@@ -1597,6 +1733,15 @@ impl<T: Clone> SignalStream<T> for RwSignal<T> {
 }
 
 impl<T> RwSignal<T> {
+    /// The node id of this signal in the reactive graph, which can be passed to
+    /// [Scope::lookup_value](crate::Scope::lookup_value) in debug builds.
+    pub fn node_id(&self) -> NodeId {
+        with_runtime(self.runtime, |runtime| runtime.signal_node(self.id))
+            .expect(
+                "tried to access a signal in a runtime that had been disposed",
+            )
+    }
+
     /// Returns a read-only handle to the signal.
     ///
     /// Useful if you're trying to give read access to another component but ensure that it can't write
@@ -1722,18 +1867,47 @@ impl<T> RwSignal<T> {
     }
 }
 
+impl<T> RwSignal<T>
+where
+    T: Debug + 'static,
+{
+    /// Marks the signal as debuggable, so that
+    /// [Scope::lookup_value](crate::Scope::lookup_value) prints its value
+    /// rather than `"<opaque>"`. This does nothing in release builds.
+    pub fn debuggable(self) -> Self {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime
+                .set_value_formatter(self.id, |value: &T| format!("{value:?}"))
+        });
+        self
+    }
+}
+
 // Internals
 slotmap::new_key_type! {
     /// Unique ID assigned to a signal.
     pub struct SignalId;
 }
 
-#[derive(Debug, Error)]
-pub(crate) enum SignalError {
+/// Describes why a signal or memo could not be read, as returned by methods
+/// like [Memo::try_with_result](crate::Memo::try_with_result).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SignalError {
+    /// The runtime the signal belongs to has been disposed.
     #[error("tried to access a signal in a runtime that had been disposed")]
     RuntimeDisposed,
+    /// The signal itself has been disposed, because the [Scope] that created
+    /// it was disposed.
     #[error("tried to access a signal that had been disposed")]
     Disposed,
+    /// The memo exists but does not currently hold a value. This happens when
+    /// a memo is read while it is computing its value for the first time, or
+    /// while a memo created with
+    /// [create_owning_memo](crate::create_owning_memo) has taken its previous
+    /// value to compute the next one.
+    #[error("tried to access a memo that has not finished computing a value")]
+    Uninitialized,
+    /// The signal does not hold a value of the expected type.
     #[error("error casting signal to type {0}")]
     Type(&'static str),
 }
@@ -1872,22 +2046,7 @@ impl SignalId {
 
             // notify subscribers
             if updated.is_some() {
-                let subs = {
-                    let subs = runtime.signal_subscribers.borrow();
-                    let subs = subs.get(*self);
-                    subs.map(|subs| subs.borrow().clone())
-                };
-                if let Some(subs) = subs {
-                    for sub in subs {
-                        let effect = {
-                            let effects = runtime.effects.borrow();
-                            effects.get(sub).cloned()
-                        };
-                        if let Some(effect) = effect {
-                            effect.run(sub, runtime_id);
-                        }
-                    }
-                }
+                runtime.notify_subscribers(*self, runtime_id, &HashSet::new());
             };
             updated
         })
@@ -29,7 +29,6 @@ where
 /// ## Core Trait Implementations
 /// - [`.set()`](#impl-SignalSet<T>-for-SignalSetter<T>) (or calling the setter as a function)
 ///   sets the signal’s value, and notifies all subscribers that the signal’s value has changed.
-///   to subscribe to the signal, and to re-run whenever the value of the signal changes.
 ///
 /// ## Examples
 /// ```rust
@@ -168,9 +167,9 @@ where
     ///
     /// // this function takes any kind of signal setter
     /// fn set_to_4(setter: &SignalSetter<i32>) {
-    ///   // ✅ calling the signal sets the value
-    ///   //    it is a shorthand for arg.set()
-    ///   setter(4);
+    ///     // ✅ calling the signal sets the value
+    ///     //    it is a shorthand for arg.set()
+    ///     setter(4);
     /// }
     ///
     /// set_to_4(&set_count.into());
@@ -178,6 +177,7 @@ where
     /// set_to_4(&set_double_count);
     /// assert_eq!(count(), 8);
     /// # });
+    /// ```
     #[cfg_attr(
         debug_assertions,
         instrument(
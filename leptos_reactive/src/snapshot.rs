@@ -0,0 +1,252 @@
+#![forbid(unsafe_code)]
+use crate::{
+    runtime::with_runtime, ReadSignal, Scope, ScopeProperty, Serializable,
+    SerializationError, SignalId, WriteSignal,
+};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+};
+
+/// The serialized values of the signals owned by a [Scope], created with
+/// [Scope::snapshot] and applied again with [Scope::restore].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuntimeSnapshot {
+    values: HashMap<SignalId, String>,
+    skipped: Vec<SignalId>,
+}
+
+impl RuntimeSnapshot {
+    /// The number of signal values in the snapshot.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the snapshot contains no signal values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The serialized value of the given signal, if it was captured.
+    pub fn get(&self, id: SignalId) -> Option<&str> {
+        self.values.get(&id).map(String::as_str)
+    }
+
+    /// The signals in the scope that could not be captured, either because
+    /// they were not created with [create_serializable_signal], or because
+    /// serializing their value failed.
+    pub fn skipped(&self) -> &[SignalId] {
+        &self.skipped
+    }
+}
+
+/// Creates a signal, like [create_signal](crate::create_signal), whose value can
+/// be captured by [Scope::snapshot] and restored by [Scope::restore].
+///
+/// Signals are stored without any type information, so only signals created
+/// this way can be included in a snapshot.
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+pub fn create_serializable_signal<T>(
+    cx: Scope,
+    value: T,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Serializable + 'static,
+{
+    let (read, write) = crate::create_signal(cx, value);
+    _ = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .signal_serializers
+            .borrow_mut()
+            .insert(read.id, SignalSerializer::new::<T>())
+    });
+    (read, write)
+}
+
+impl Scope {
+    /// Captures the current values of all the signals owned by this scope that
+    /// were created with [create_serializable_signal].
+    ///
+    /// Signals owned by child scopes are not included. Any other signals are
+    /// listed in [RuntimeSnapshot::skipped]. Memos are not captured, as they
+    /// will recompute from their sources when the snapshot is restored.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_serializable_signal(cx, 0);
+    /// let double = create_memo(cx, move |_| count() * 2);
+    ///
+    /// let snapshot = cx.snapshot();
+    /// set_count(5);
+    /// assert_eq!(double(), 10);
+    ///
+    /// cx.restore(&snapshot).unwrap();
+    /// assert_eq!(count(), 0);
+    /// assert_eq!(double(), 0);
+    /// # }).dispose();
+    /// ```
+    pub fn snapshot(&self) -> RuntimeSnapshot {
+        with_runtime(self.runtime, |runtime| {
+            let signals = {
+                let scopes = runtime.scopes.borrow();
+                scopes
+                    .get(self.id)
+                    .map(|props| {
+                        props
+                            .borrow()
+                            .iter()
+                            .filter_map(|prop| match prop {
+                                // memos are derived from the signals they read
+                                ScopeProperty::Signal(id)
+                                    if !runtime.signal_node(*id).is_memo() =>
+                                {
+                                    Some(*id)
+                                }
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default()
+            };
+
+            let mut snapshot = RuntimeSnapshot::default();
+            for id in signals {
+                let serializer =
+                    runtime.signal_serializers.borrow().get(id).copied();
+                let value = runtime.signals.borrow().get(id).cloned();
+                let serialized =
+                    serializer.zip(value).and_then(|(serializer, value)| {
+                        (serializer.ser)(&*value.borrow()).ok()
+                    });
+                match serialized {
+                    Some(serialized) => {
+                        snapshot.values.insert(id, serialized);
+                    }
+                    None => snapshot.skipped.push(id),
+                }
+            }
+            snapshot
+        })
+        .unwrap_or_default()
+    }
+
+    /// Writes the values captured by [Scope::snapshot] back into their signals.
+    ///
+    /// Every value is deserialized before any signal is changed, so if any value
+    /// fails to deserialize, no signals are changed. Once all the values have
+    /// been written, the memos that depend on the signals are brought up to
+    /// date, and then each effect that depends on any of the signals, directly
+    /// or through a memo, runs once. Signals that have since been disposed are
+    /// ignored.
+    pub fn restore(
+        &self,
+        snapshot: &RuntimeSnapshot,
+    ) -> Result<(), SerializationError> {
+        with_runtime(self.runtime, |runtime| {
+            let mut values = Vec::with_capacity(snapshot.values.len());
+            for (id, serialized) in &snapshot.values {
+                let serializer =
+                    runtime.signal_serializers.borrow().get(*id).copied();
+                if let Some(serializer) = serializer {
+                    values.push((
+                        *id,
+                        serializer,
+                        (serializer.de)(serialized)?,
+                    ));
+                }
+            }
+
+            let mut subscribers = Vec::new();
+            for (id, serializer, value) in values {
+                let signal = runtime.signals.borrow().get(id).cloned();
+                if let Some(signal) = signal {
+                    (serializer.set)(&mut *signal.borrow_mut(), value);
+                    if let Some(subs) =
+                        runtime.signal_subscribers.borrow().get(id)
+                    {
+                        subscribers.extend(subs.borrow().iter().copied());
+                    }
+                }
+            }
+
+            // run memos first, so that effects see their new values, and
+            // hold the effects they notify so that each effect runs once
+            let mut seen = HashSet::new();
+            subscribers.retain(|id| seen.insert(*id));
+            let (memos, effects): (Vec<_>, Vec<_>) = subscribers
+                .into_iter()
+                .partition(|id| runtime.memo_effects.borrow().contains_key(id));
+            let held = runtime.held_effects.replace(Some(effects));
+
+            runtime.scheduler.enter_flush();
+            for id in memos {
+                let effect = runtime.effects.borrow().get(id).cloned();
+                if let Some(effect) = effect {
+                    runtime.scheduler.record_node();
+                    effect.run(id, self.runtime);
+                }
+            }
+            let effects =
+                runtime.held_effects.replace(held).unwrap_or_default();
+            for id in effects {
+                let effect = runtime.effects.borrow().get(id).cloned();
+                if let Some(effect) = effect {
+                    if runtime.defer_effect(id, self.runtime) {
+                        continue;
+                    }
+                    runtime.scheduler.record_node();
+                    effect.run(id, self.runtime);
+                }
+            }
+            runtime.scheduler.exit_flush();
+            Ok(())
+        })
+        .unwrap_or(Ok(()))
+    }
+}
+
+/// Type-erased functions for serializing and deserializing the value of a
+/// signal created with [create_serializable_signal].
+#[derive(Clone, Copy)]
+pub(crate) struct SignalSerializer {
+    ser: fn(&dyn Any) -> Result<String, SerializationError>,
+    de: fn(&str) -> Result<Box<dyn Any>, SerializationError>,
+    set: fn(&mut dyn Any, Box<dyn Any>),
+}
+
+impl SignalSerializer {
+    fn new<T>() -> Self
+    where
+        T: Serializable + 'static,
+    {
+        Self {
+            ser: |value| {
+                value
+                    .downcast_ref::<T>()
+                    .expect("signal has the wrong type")
+                    .ser()
+            },
+            de: |serialized| {
+                T::de(serialized).map(|value| Box::new(value) as Box<dyn Any>)
+            },
+            set: |value, new_value| {
+                if let (Some(value), Ok(new_value)) =
+                    (value.downcast_mut::<T>(), new_value.downcast::<T>())
+                {
+                    *value = *new_value;
+                }
+            },
+        }
+    }
+}
@@ -1,6 +1,6 @@
 #![forbid(unsafe_code)]
 use cfg_if::cfg_if;
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 /// Spawns and runs a thread-local [std::future::Future] in a platform-independent way.
 ///
@@ -22,3 +22,51 @@ where
         }
     }
 }
+
+/// Returns a future that resolves after `duration` has elapsed, in a
+/// platform-independent way.
+///
+/// On native platforms without an async runtime, where [spawn_local] blocks
+/// until its future completes, there is no event loop to wait on, so the
+/// future resolves immediately.
+pub(crate) fn sleep(duration: Duration) -> impl Future<Output = ()> {
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                _ = web_sys::window()
+                    .expect("no window")
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        &resolve,
+                        duration.as_millis() as i32,
+                    );
+            });
+            async move {
+                _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+            }
+        } else if #[cfg(feature = "ssr")] {
+            tokio::time::sleep(duration)
+        } else {
+            _ = duration;
+            futures::future::ready(())
+        }
+    }
+}
+
+/// Runs `f` once `duration` has elapsed, in a platform-independent way.
+///
+/// On native platforms without an async runtime, where [spawn_local] blocks
+/// until its future completes, there is no event loop to come back to, so `f`
+/// runs immediately.
+pub(crate) fn set_timeout(f: impl FnOnce() + 'static, duration: Duration) {
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate", feature = "ssr"))] {
+            spawn_local(async move {
+                sleep(duration).await;
+                f();
+            });
+        } else {
+            _ = duration;
+            f();
+        }
+    }
+}
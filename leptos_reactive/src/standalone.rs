@@ -0,0 +1,99 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_memo, create_runtime, create_signal, raw_scope_and_disposer, Memo,
+    ReadSignal, RuntimeId, ScopeDisposer, WriteSignal,
+};
+use std::cell::Cell;
+
+thread_local! {
+    // the runtime shared by standalone nodes, and how many of them are alive
+    static STANDALONE: Cell<Option<(RuntimeId, usize)>> = Default::default();
+}
+
+/// Creates a memo without a [Scope](crate::Scope), along with an
+/// [OwnerGuard] that disposes of it when it is dropped.
+///
+/// This is meant for unit-testing computations without wrapping them in
+/// `create_scope(create_runtime(), |cx| { ... }).dispose()`. Standalone memos
+/// and signals, created with [create_signal_standalone], share a runtime on
+/// each thread, so a standalone memo tracks the standalone signals it reads.
+/// The runtime itself is disposed once every guard has been dropped.
+///
+/// The memo can’t be used once its guard has been dropped: reading it then
+/// panics, like reading a memo whose scope has been disposed.
+/// ```
+/// # use leptos_reactive::*;
+/// let ((count, set_count), _count) = create_signal_standalone(1);
+/// let (double, _double) = create_memo_standalone(move |_| count() * 2);
+///
+/// set_count(2);
+/// assert_eq!(double(), 4);
+/// ```
+#[track_caller]
+pub fn create_memo_standalone<T>(
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> (Memo<T>, OwnerGuard)
+where
+    T: PartialEq + 'static,
+{
+    let (cx, disposer) = raw_scope_and_disposer(OwnerGuard::acquire());
+    (create_memo(cx, f), OwnerGuard(Some(disposer)))
+}
+
+/// Creates a signal without a [Scope](crate::Scope), along with an
+/// [OwnerGuard] that disposes of it when it is dropped. Memos created with
+/// [create_memo_standalone] can depend on it.
+///
+/// The signal can’t be used once its guard has been dropped.
+#[track_caller]
+pub fn create_signal_standalone<T>(
+    value: T,
+) -> ((ReadSignal<T>, WriteSignal<T>), OwnerGuard)
+where
+    T: 'static,
+{
+    let (cx, disposer) = raw_scope_and_disposer(OwnerGuard::acquire());
+    (create_signal(cx, value), OwnerGuard(Some(disposer)))
+}
+
+/// Owns a memo or signal created without a [Scope](crate::Scope), like one
+/// created with [create_memo_standalone], and disposes of it when dropped.
+///
+/// Dropping the guard does the same as disposing of the node’s scope, and
+/// also disposes of the shared standalone runtime if this was the last guard.
+#[must_use = "the node is disposed as soon as its guard is dropped"]
+pub struct OwnerGuard(Option<ScopeDisposer>);
+
+impl OwnerGuard {
+    /// The standalone runtime, which is created if there isn’t one.
+    fn acquire() -> RuntimeId {
+        STANDALONE.with(|standalone| {
+            let (runtime, guards) =
+                standalone.get().unwrap_or_else(|| (create_runtime(), 0));
+            standalone.set(Some((runtime, guards + 1)));
+            runtime
+        })
+    }
+}
+
+impl Drop for OwnerGuard {
+    fn drop(&mut self) {
+        if let Some(disposer) = self.0.take() {
+            disposer.dispose();
+        }
+        let released = STANDALONE.with(|standalone| match standalone.get() {
+            Some((runtime, 1)) => {
+                standalone.set(None);
+                Some(runtime)
+            }
+            Some((runtime, guards)) => {
+                standalone.set(Some((runtime, guards - 1)));
+                None
+            }
+            None => None,
+        });
+        if let Some(runtime) = released {
+            runtime.dispose();
+        }
+    }
+}
@@ -0,0 +1,108 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_memo, create_rw_signal, Memo, RwSignal, Scope, Signal, SignalUpdate,
+    SignalWith, SignalWithUntracked,
+};
+
+/// Creates a [Stepper], a reactive cursor that moves through a fixed number
+/// of steps, as in a multi-step form or wizard.
+///
+/// The current step always stays within `0..steps`: calling [Stepper::next]
+/// on the last step or [Stepper::prev] on the first step does nothing.
+///
+/// # Panics
+/// Panics if `steps` is `0`, since there would be no current step.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let stepper = create_stepper(cx, 3);
+/// assert_eq!(stepper.current()(), 0);
+/// assert_eq!(stepper.can_prev()(), false);
+/// assert_eq!(stepper.can_next()(), true);
+///
+/// stepper.next();
+/// stepper.next();
+/// assert_eq!(stepper.current()(), 2);
+/// assert_eq!(stepper.can_next()(), false);
+///
+/// // ✅ advancing past the last step is prevented
+/// stepper.next();
+/// assert_eq!(stepper.current()(), 2);
+///
+/// stepper.prev();
+/// assert_eq!(stepper.current()(), 1);
+/// assert_eq!(stepper.can_prev()(), true);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    debug_assertions,
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            cx = ?cx.id,
+        )
+    )
+)]
+#[track_caller]
+pub fn create_stepper(cx: Scope, steps: usize) -> Stepper {
+    assert!(steps > 0, "a stepper needs at least one step");
+    let current = create_rw_signal(cx, 0);
+    let can_next = create_memo(cx, move |_| current.with(|n| n + 1 < steps));
+    let can_prev = create_memo(cx, move |_| current.with(|n| *n > 0));
+    Stepper {
+        steps,
+        current,
+        can_next,
+        can_prev,
+    }
+}
+
+/// A reactive cursor over a fixed number of steps, created with [create_stepper].
+///
+/// Like the signal types, `Stepper` is [Copy] and `'static`, so it can easily be
+/// moved into closures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stepper {
+    steps: usize,
+    current: RwSignal<usize>,
+    can_next: Memo<bool>,
+    can_prev: Memo<bool>,
+}
+
+impl Stepper {
+    /// The total number of steps.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The index of the current step, in `0..steps`.
+    pub fn current(&self) -> Signal<usize> {
+        self.current.into()
+    }
+
+    /// Whether there is a step after the current one.
+    pub fn can_next(&self) -> Signal<bool> {
+        self.can_next.into()
+    }
+
+    /// Whether there is a step before the current one.
+    pub fn can_prev(&self) -> Signal<bool> {
+        self.can_prev.into()
+    }
+
+    /// Advances to the next step, if there is one.
+    pub fn next(&self) {
+        let steps = self.steps;
+        if self.current.with_untracked(|n| n + 1 < steps) {
+            self.current.update(|n| *n += 1);
+        }
+    }
+
+    /// Returns to the previous step, if there is one.
+    pub fn prev(&self) {
+        if self.current.with_untracked(|n| *n > 0) {
+            self.current.update(|n| *n -= 1);
+        }
+    }
+}
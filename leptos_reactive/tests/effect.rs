@@ -91,3 +91,357 @@ fn untrack_mutes_effect() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn isomorphic_effect_cleanup_runs_on_dispose() {
+    use leptos_reactive::{on_cleanup, raw_scope_and_disposer};
+    use std::{cell::Cell, rc::Rc};
+
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (a, set_a) = create_signal(cx, 0);
+    let runs = Rc::new(Cell::new(0));
+    let cleanups = Rc::new(Cell::new(0));
+
+    create_isomorphic_effect(cx, {
+        let runs = runs.clone();
+        move |_| {
+            a();
+            runs.set(runs.get() + 1);
+        }
+    });
+    on_cleanup(cx, {
+        let cleanups = cleanups.clone();
+        move || cleanups.set(cleanups.get() + 1)
+    });
+
+    assert_eq!(runs.get(), 1);
+    set_a(1);
+    assert_eq!(runs.get(), 2);
+    assert_eq!(cleanups.get(), 0);
+
+    disposer.dispose();
+    assert_eq!(cleanups.get(), 1);
+}
+
+#[cfg(all(not(feature = "stable"), feature = "ssr"))]
+#[test]
+fn only_isomorphic_effects_run_on_server() {
+    use leptos_reactive::create_effect;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let effect_runs = Rc::new(Cell::new(0));
+        let isomorphic_runs = Rc::new(Cell::new(0));
+
+        create_effect(cx, {
+            let effect_runs = effect_runs.clone();
+            move |_| {
+                a();
+                effect_runs.set(effect_runs.get() + 1);
+            }
+        });
+        create_isomorphic_effect(cx, {
+            let isomorphic_runs = isomorphic_runs.clone();
+            move |_| {
+                a();
+                isomorphic_runs.set(isomorphic_runs.get() + 1);
+            }
+        });
+
+        set_a(1);
+        assert_eq!(effect_runs.get(), 0);
+        assert_eq!(isomorphic_runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), not(feature = "ssr")))]
+#[test]
+fn effect_retry_jitter_retries_until_success() {
+    use leptos_reactive::{create_effect_retry_jitter, RetryPolicy};
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    create_scope(create_runtime(), |cx| {
+        let attempts = Rc::new(Cell::new(0));
+        create_effect_retry_jitter(
+            cx,
+            RetryPolicy {
+                max_retries: 5,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(4),
+            },
+            {
+                let attempts = Rc::clone(&attempts);
+                move |_| {
+                    attempts.set(attempts.get() + 1);
+                    if attempts.get() < 3 {
+                        Err(())
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        );
+        assert_eq!(attempts.get(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), not(feature = "ssr")))]
+#[test]
+fn effect_retry_jitter_gives_up_after_max_retries() {
+    use leptos_reactive::{create_effect_retry_jitter, RetryPolicy};
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let attempts = Rc::new(Cell::new(0));
+        let policy = RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+        };
+        create_effect_retry_jitter(cx, policy, {
+            let attempts = Rc::clone(&attempts);
+            move |_| {
+                a();
+                attempts.set(attempts.get() + 1);
+                Err::<(), _>(())
+            }
+        });
+        // the first run, plus two retries
+        assert_eq!(attempts.get(), 3);
+
+        // a change to a dependency starts over
+        set_a(1);
+        assert_eq!(attempts.get(), 6);
+
+        for attempt in 0..8 {
+            let delay = policy.delay(attempt);
+            assert!(delay >= Duration::from_micros(500));
+            assert!(delay <= Duration::from_millis(4));
+        }
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn scheduler_defers_effects_until_flushed() {
+    use leptos_reactive::set_scheduler;
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    create_scope(create_runtime(), |cx| {
+        let queue = Rc::new(RefCell::new(Vec::<Box<dyn FnOnce()>>::new()));
+        set_scheduler(cx, {
+            let queue = Rc::clone(&queue);
+            move |flush| queue.borrow_mut().push(flush)
+        });
+
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() * 2);
+        let runs = Rc::new(Cell::new(0));
+        let seen = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            let seen = Rc::clone(&seen);
+            move |_| {
+                runs.set(runs.get() + 1);
+                seen.set(b());
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        set_a(1);
+        set_a(2);
+        // memos stay synchronous, effects wait for the next tick
+        assert_eq!(b(), 4);
+        assert_eq!(runs.get(), 1);
+        assert_eq!(queue.borrow().len(), 1);
+
+        for flush in queue.take() {
+            flush();
+        }
+        assert_eq!(runs.get(), 2);
+        assert_eq!(seen.get(), 4);
+        assert!(queue.borrow().is_empty());
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn paused_effects_run_once_on_resume() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() * 2);
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                b();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        cx.pause_effects();
+        set_a(1);
+        set_a(2);
+        // memos are still up to date while effects are paused
+        assert_eq!(b(), 4);
+        assert_eq!(runs.get(), 1);
+
+        cx.resume_effects();
+        assert_eq!(runs.get(), 2);
+
+        // resuming again does nothing
+        cx.resume_effects();
+        set_a(3);
+        assert_eq!(runs.get(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn pausing_effects_can_be_nested() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+        cx.pause_effects();
+        cx.pause_effects();
+
+        // effects created while paused run once, then wait
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                a();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        set_a(1);
+        cx.resume_effects();
+        assert_eq!(runs.get(), 1);
+        cx.resume_effects();
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn pausing_effects_pauses_child_scopes() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let parent_runs = Rc::new(Cell::new(0));
+        let child_runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let parent_runs = Rc::clone(&parent_runs);
+            move |_| {
+                a();
+                parent_runs.set(parent_runs.get() + 1);
+            }
+        });
+        let (child, _) = cx.run_child_scope(|cx| {
+            create_isomorphic_effect(cx, {
+                let child_runs = Rc::clone(&child_runs);
+                move |_| {
+                    a();
+                    child_runs.set(child_runs.get() + 1);
+                }
+            });
+            cx
+        });
+
+        child.pause_effects();
+        set_a(1);
+        assert_eq!(parent_runs.get(), 2);
+        assert_eq!(child_runs.get(), 1);
+
+        cx.pause_effects();
+        child.resume_effects();
+        set_a(2);
+        // still paused by the parent
+        assert_eq!(child_runs.get(), 1);
+        cx.resume_effects();
+        assert_eq!(parent_runs.get(), 3);
+        assert_eq!(child_runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), not(feature = "ssr")))]
+#[test]
+fn effect_with_cleanup_cleans_up_previous_values() {
+    use leptos_reactive::create_effect_with_cleanup;
+    use std::{cell::RefCell, rc::Rc};
+
+    let cleaned = Rc::new(RefCell::new(Vec::new()));
+    create_scope(create_runtime(), {
+        let cleaned = Rc::clone(&cleaned);
+        move |cx| {
+            let (a, set_a) = create_signal(cx, 0);
+            create_effect_with_cleanup(cx, move || a(), {
+                let cleaned = Rc::clone(&cleaned);
+                move |value| cleaned.borrow_mut().push(value)
+            });
+            assert!(cleaned.borrow().is_empty());
+
+            set_a(1);
+            set_a(2);
+            assert_eq!(*cleaned.borrow(), [0, 1]);
+        }
+    })
+    .dispose();
+
+    // the last value is cleaned up exactly once when the scope is disposed
+    assert_eq!(*cleaned.borrow(), [0, 1, 2]);
+}
+
+#[cfg(all(not(feature = "stable"), not(feature = "ssr")))]
+#[test]
+fn effect_on_tracks_only_its_deps() {
+    use leptos_reactive::create_effect_on;
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, 10);
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        create_effect_on(
+            cx,
+            move || a(),
+            {
+                let log = Rc::clone(&log);
+                move |a, prev: Option<i32>| {
+                    log.borrow_mut().push((a, prev));
+                    a + b()
+                }
+            },
+            false,
+        );
+
+        set_b(20);
+        set_a(2);
+        set_b(30);
+        set_a(3);
+
+        // each run gets the value the previous run returned
+        assert_eq!(*log.borrow(), [(1, None), (2, Some(11)), (3, Some(22))]);
+    })
+    .dispose()
+}
@@ -92,3 +92,1999 @@ fn memo_runs_only_when_inputs_change() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+#[should_panic(expected = "exceeded the maximum memo depth of 2")]
+fn memo_chain_deeper_than_max_depth_panics() {
+    let runtime = create_runtime();
+    runtime.set_max_memo_depth(2);
+
+    create_scope(runtime, |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() + 1);
+        let c = create_memo(cx, move |_| b() + 1);
+        let d = create_memo(cx, move |_| c() + 1);
+        assert_eq!(d(), 3);
+
+        // a -> b -> c -> d is three memos deep
+        set_a(1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_chain_within_max_depth_runs() {
+    let runtime = create_runtime();
+    runtime.set_max_memo_depth(3);
+
+    create_scope(runtime, |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() + 1);
+        let c = create_memo(cx, move |_| b() + 1);
+        let d = create_memo(cx, move |_| c() + 1);
+        set_a(1);
+        assert_eq!(d(), 4);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), not(feature = "ssr")))]
+#[test]
+fn throttled_memo_stream_emits_leading_and_trailing_values() {
+    use futures::StreamExt;
+    use leptos_reactive::SignalSet;
+    use std::time::Duration;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let double = create_memo(cx, move |_| a() * 2);
+        let stream = double.to_stream_throttled(cx, Duration::from_millis(20));
+
+        // these changes arrive during the cooldown started by the first value
+        set_a.set(1);
+        set_a.set(2);
+
+        let values =
+            futures::executor::block_on(stream.take(2).collect::<Vec<_>>());
+        assert_eq!(values, vec![0, 4]);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_history_only_notifies_on_distinct_values() {
+    use leptos_reactive::{create_isomorphic_effect, create_memo_history};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let history = create_memo_history(cx, 2, move |_| a());
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                history();
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        set_a(1);
+        set_a(1);
+        assert_eq!(history(), [0, 1]);
+        assert_eq!(notified.get(), 2);
+
+        set_a(2);
+        assert_eq!(history(), [1, 2]);
+        assert_eq!(notified.get(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), debug_assertions))]
+#[test]
+#[should_panic(expected = "capacity of at least 1")]
+fn memo_history_rejects_zero_capacity() {
+    use leptos_reactive::create_memo_history;
+
+    create_scope(create_runtime(), |cx| {
+        create_memo_history(cx, 0, |_| 0);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_cleanups_run_before_recomputing_and_on_dispose() {
+    use leptos_reactive::on_cleanup;
+    use std::{cell::RefCell, rc::Rc};
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    create_scope(create_runtime(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let (a, set_a) = create_signal(cx, 0);
+            let b = create_memo(cx, {
+                let log = Rc::clone(&log);
+                move |_| {
+                    let a = a();
+                    log.borrow_mut().push(format!("compute {a}"));
+                    on_cleanup(cx, {
+                        let log = Rc::clone(&log);
+                        move || log.borrow_mut().push(format!("cleanup {a}"))
+                    });
+                    a
+                }
+            });
+            set_a(1);
+            set_a(2);
+            assert_eq!(b(), 2);
+        }
+    })
+    .dispose();
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "compute 0",
+            "cleanup 0",
+            "compute 1",
+            "cleanup 1",
+            "compute 2",
+            "cleanup 2"
+        ]
+    );
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_watcher_stops_when_handle_is_dropped() {
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() / 2);
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let handle = b.watch(cx, {
+            let log = Rc::clone(&log);
+            move |new, old| log.borrow_mut().push((*new, old.copied()))
+        });
+
+        set_a(1); // b is unchanged
+        set_a(2);
+        set_a(4);
+        assert_eq!(*log.borrow(), [(0, None), (1, Some(0)), (2, Some(1))]);
+
+        drop(handle);
+        set_a(6);
+        assert_eq!(log.borrow().len(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), debug_assertions))]
+#[test]
+#[should_panic(expected = "reactive cycle detected")]
+fn memo_that_updates_its_own_source_panics() {
+    use leptos_reactive::create_isomorphic_effect;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() + 1);
+        // b -> effect -> a -> b
+        create_isomorphic_effect(cx, move |_| set_a(b()));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_always_notifies_without_partial_eq() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_memo_always, SignalWith,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    struct NotEq(i32);
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+        let b = create_memo_always(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                NotEq(a() / 2)
+            }
+        });
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                b.with(|_| {});
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        // reading does not recompute
+        assert_eq!(b.with(|b| b.0), 0);
+        assert_eq!(b.with(|b| b.0), 0);
+        assert_eq!(runs.get(), 1);
+
+        // same value, but dependents are still notified
+        set_a(1);
+        assert_eq!(b.with(|b| b.0), 0);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(notified.get(), 2);
+
+        // memos are compared by identity
+        let c = b;
+        assert!(b == c);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_over_non_clone_value_is_usable_through_with() {
+    use leptos_reactive::{SignalWith, SignalWithUntracked};
+
+    #[derive(PartialEq)]
+    struct NotClone(i32);
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let b = create_memo(cx, move |_| NotClone(a() * 2));
+        let c = b.map(cx, |b| b.0 + 1);
+
+        assert_eq!(b.with(|b| b.0), 2);
+        set_a(2);
+        assert_eq!(b.with_untracked(|b| b.0), 4);
+        assert_eq!(b.map_untracked(|b| b.0), 4);
+        assert_eq!(b.try_with(|b| b.0), Some(4));
+        assert_eq!(c(), 5);
+        assert!(format!("{b:?}").starts_with("Memo"));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn owning_memo_reuses_previous_value() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_owning_memo, SignalWith,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let prevs = Rc::new(Cell::new(0));
+        let buf = create_owning_memo(cx, {
+            let prevs = Rc::clone(&prevs);
+            move |prev: Option<Vec<i32>>| {
+                if prev.is_some() {
+                    prevs.set(prevs.get() + 1);
+                }
+                let mut buf = prev.unwrap_or_default();
+                let changed = buf.last() != Some(&(a() / 2));
+                if changed {
+                    buf.push(a() / 2);
+                }
+                (buf, changed)
+            }
+        });
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                buf.with(|_| {});
+                notified.set(notified.get() + 1);
+            }
+        });
+        assert_eq!(prevs.get(), 0);
+
+        set_a(2);
+        assert_eq!(buf.with(Vec::clone), [0, 1]);
+        assert_eq!(notified.get(), 2);
+
+        // unchanged: the value is kept, but nothing is notified
+        set_a(3);
+        assert_eq!(buf.with(Vec::clone), [0, 1]);
+        assert_eq!(notified.get(), 2);
+        assert_eq!(prevs.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn filter_map_memo_starts_with_initial_when_first_value_is_none() {
+    use leptos_reactive::{create_filter_map_memo, create_isomorphic_effect};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, None);
+        let b = create_filter_map_memo(cx, -1, move || a());
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                b();
+                notified.set(notified.get() + 1);
+            }
+        });
+        assert_eq!(b(), -1);
+
+        set_a(Some(1));
+        assert_eq!(b(), 1);
+        assert_eq!(notified.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn filter_map_memo_keeps_last_value_on_none() {
+    use leptos_reactive::{create_filter_map_memo, create_isomorphic_effect};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, None);
+        let b = create_filter_map_memo(cx, -1, move || a());
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                b();
+                notified.set(notified.get() + 1);
+            }
+        });
+        set_a(Some(1));
+        set_a(None);
+        assert_eq!(b(), 1);
+        assert_eq!(notified.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn filter_map_memo_does_not_notify_for_the_same_value() {
+    use leptos_reactive::{create_filter_map_memo, create_isomorphic_effect};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, None);
+        let b = create_filter_map_memo(cx, -1, move || a());
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                b();
+                notified.set(notified.get() + 1);
+            }
+        });
+        set_a(Some(1));
+        set_a(Some(1));
+        assert_eq!(b(), 1);
+        assert_eq!(notified.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[cfg(debug_assertions)]
+#[test]
+fn memo_dependencies_and_subscribers() {
+    use leptos_reactive::create_isomorphic_effect;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let (flag, set_flag) = create_signal(cx, true);
+        let b = create_memo(cx, move |_| a() + 1);
+        let c = create_memo(cx, move |_| if flag() { b() } else { 0 });
+        create_isomorphic_effect(cx, move |_| c());
+
+        let b_deps = b.dependencies();
+        assert_eq!(b_deps.len(), 1);
+        assert!(b_deps[0].is_signal());
+        assert_eq!(b.subscribers(), vec![c.node_id()]);
+
+        let c_deps = c.dependencies();
+        assert_eq!(c_deps.len(), 2);
+        assert!(c_deps.contains(&b.node_id()));
+        assert!(c.node_id().is_memo());
+        assert_eq!(c.subscribers().len(), 1);
+        assert!(c.subscribers()[0].is_effect());
+
+        // dependencies are those of the latest computation
+        set_a(1);
+        assert_eq!(b.dependencies(), b_deps);
+        set_flag(false);
+        assert_eq!(c.dependencies().len(), 1);
+        assert!(b.subscribers().is_empty());
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[cfg(debug_assertions)]
+#[test]
+fn graph_to_dot_renders_nodes_and_edges() {
+    use leptos_reactive::create_isomorphic_effect;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, _) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() + 1);
+        create_isomorphic_effect(cx, move |_| b());
+
+        let dot = cx.graph_to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with('}'));
+        assert_eq!(dot.matches("shape=ellipse").count(), 1);
+        assert_eq!(dot.matches("shape=box").count(), 1);
+        assert_eq!(dot.matches("shape=diamond").count(), 1);
+        assert_eq!(dot.matches(" -> ").count(), 2);
+        assert!(dot.contains(&format!("-> \"{}\"", b.node_id())));
+        assert!(dot.contains("tests/memo.rs"));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn frozen_memo_ignores_sources_until_thawed() {
+    use leptos_reactive::create_isomorphic_effect;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let runs = Rc::new(Cell::new(0));
+        let b = create_memo(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                a() * 2
+            }
+        });
+        let seen = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let seen = Rc::clone(&seen);
+            move |_| seen.set(b())
+        });
+
+        b.freeze();
+        assert!(b.is_frozen());
+        set_a(2);
+        set_a(3);
+        assert_eq!(b(), 2);
+        assert_eq!(runs.get(), 1);
+
+        b.thaw();
+        assert!(!b.is_frozen());
+        assert_eq!(b(), 6);
+        assert_eq!(seen.get(), 6);
+        assert_eq!(runs.get(), 2);
+
+        // thawing again does nothing
+        b.thaw();
+        assert_eq!(runs.get(), 2);
+        set_a(4);
+        assert_eq!(seen.get(), 8);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn hashed_memo_compares_keys() {
+    use leptos_reactive::{create_isomorphic_effect, create_memo_hashed};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, (1, "a"));
+        let b = create_memo_hashed(cx, |(version, _)| *version, move |_| a());
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                b();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        set_a((2, "b"));
+        assert_eq!(b(), (2, "b"));
+        assert_eq!(runs.get(), 2);
+
+        // same key, so dependents are not notified, but the value is stored
+        set_a((2, "c"));
+        assert_eq!(runs.get(), 2);
+        assert_eq!(b(), (2, "c"));
+
+        // different values with different keys always notify
+        set_a((3, "c"));
+        assert_eq!(runs.get(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[cfg(feature = "catch-panics")]
+#[test]
+fn panic_hook_recovers_memo() {
+    use leptos_reactive::set_reactive_panic_hook;
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let panics = Rc::new(RefCell::new(Vec::new()));
+        set_reactive_panic_hook(cx, {
+            let panics = Rc::clone(&panics);
+            move |node, payload| {
+                assert!(node.is_memo());
+                panics
+                    .borrow_mut()
+                    .push(payload.downcast_ref::<&str>().unwrap().to_string());
+            }
+        });
+
+        let (a, set_a) = create_signal(cx, 1);
+        let b = create_memo(cx, move |_| {
+            if a() < 0 {
+                panic!("negative");
+            }
+            a() * 2
+        });
+
+        set_a(-1);
+        assert_eq!(b(), 2);
+        assert_eq!(*panics.borrow(), ["negative"]);
+
+        // the memo recomputes when its sources change again
+        set_a(2);
+        assert_eq!(b(), 4);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[cfg(feature = "catch-panics")]
+#[test]
+fn panic_hook_resumes_panic_without_previous_value() {
+    use leptos_reactive::set_reactive_panic_hook;
+    use std::{cell::Cell, rc::Rc};
+
+    let hooked = Rc::new(Cell::new(false));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        create_scope(create_runtime(), {
+            let hooked = Rc::clone(&hooked);
+            move |cx| {
+                set_reactive_panic_hook(cx, move |_, _| hooked.set(true));
+                create_memo(cx, |_| -> i32 { panic!("first run") });
+            }
+        })
+    }));
+    assert!(result.is_err());
+    assert!(hooked.get());
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_with_initial_skips_first_computation() {
+    use leptos_reactive::{create_isomorphic_effect, create_memo_with_initial};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let computed = Rc::new(Cell::new(0));
+        let b = create_memo_with_initial(cx, 0, move || a(), {
+            let computed = Rc::clone(&computed);
+            move |a, _| {
+                computed.set(computed.get() + 1);
+                a * 10
+            }
+        });
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                b();
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        assert_eq!(b(), 0);
+        assert_eq!(computed.get(), 0);
+
+        set_a(2);
+        assert_eq!(b(), 20);
+        assert_eq!(computed.get(), 1);
+        assert_eq!(notified.get(), 2);
+
+        set_a(3);
+        assert_eq!(b(), 30);
+        assert_eq!(computed.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_with_initial_does_not_notify_if_first_value_is_equal() {
+    use leptos_reactive::{create_isomorphic_effect, create_memo_with_initial};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let b = create_memo_with_initial(cx, 0, move || a(), |a, _| a - a);
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                b();
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        set_a(2);
+        assert_eq!(b(), 0);
+        assert_eq!(notified.get(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn keyed_diff_reorder() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_keyed_diff, CollectionDiff, SignalWith,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (items, set_items) = create_signal(cx, vec![1, 2, 3]);
+        let diff = create_keyed_diff(cx, move || items(), |n| *n);
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                diff.with(|_| ());
+                notified.set(notified.get() + 1);
+            }
+        });
+        assert_eq!(diff.with(|diff| diff.added.clone()), [1, 2, 3]);
+
+        set_items(vec![3, 2, 1]);
+        assert_eq!(
+            diff(),
+            CollectionDiff {
+                added: vec![],
+                removed: vec![],
+                moved: vec![3, 1],
+            }
+        );
+        assert_eq!(notified.get(), 2);
+
+        // same keys in the same order
+        set_items(vec![3, 2, 1]);
+        assert_eq!(notified.get(), 2);
+        assert_eq!(diff.with(|diff| diff.moved.clone()), [3, 1]);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn keyed_diff_insert_in_middle() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_keyed_diff, CollectionDiff, SignalWith,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (items, set_items) = create_signal(cx, vec![1, 2, 3]);
+        let diff = create_keyed_diff(cx, move || items(), |n| *n);
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                diff.with(|_| ());
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        set_items(vec![1, 4, 5, 2, 3]);
+        assert_eq!(
+            diff(),
+            CollectionDiff {
+                added: vec![4, 5],
+                removed: vec![],
+                moved: vec![],
+            }
+        );
+        assert_eq!(notified.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn keyed_diff_full_clear() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_keyed_diff, CollectionDiff, SignalWith,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (items, set_items) = create_signal(cx, vec![1, 2, 3]);
+        let diff = create_keyed_diff(cx, move || items(), |n| *n);
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                diff.with(|_| ());
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        set_items(vec![]);
+        assert_eq!(
+            diff(),
+            CollectionDiff {
+                added: vec![],
+                removed: vec![1, 2, 3],
+                moved: vec![],
+            }
+        );
+        assert_eq!(notified.get(), 2);
+
+        // clearing an empty collection changes nothing
+        set_items(vec![]);
+        assert_eq!(notified.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn arc_memo_shares_value_until_changed() {
+    use leptos_reactive::{create_isomorphic_effect, create_memo_arc};
+    use std::{cell::Cell, rc::Rc, sync::Arc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 2);
+        let b = create_memo_arc(cx, move |_| vec![a() % 2; 3]);
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                b.get_arc();
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        let first = b.get_arc();
+        assert!(Arc::ptr_eq(&first, &b.get_arc()));
+
+        // an equal value keeps the same allocation, and doesn't notify
+        set_a(4);
+        assert!(Arc::ptr_eq(&first, &b.get_arc()));
+        assert_eq!(notified.get(), 1);
+
+        set_a(5);
+        assert_eq!(*b.get_arc(), [1, 1, 1]);
+        assert!(!Arc::ptr_eq(&first, &b.get_arc()));
+        assert_eq!(notified.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_resolves_context_in_its_own_scope() {
+    use leptos_reactive::{provide_context, use_context};
+
+    create_scope(create_runtime(), |cx| {
+        provide_context(cx, "root");
+        let (a, set_a) = create_signal(cx, 0);
+
+        let (memo, _) = cx.run_child_scope(|cx| {
+            provide_context(cx, "child");
+            create_memo(cx, move |_| (a(), use_context::<&str>(cx)))
+        });
+        assert_eq!(memo(), (0, Some("child")));
+
+        // updated from a sibling scope with its own context
+        let _ = cx.run_child_scope(|cx| {
+            provide_context(cx, "sibling");
+            set_a(1);
+        });
+        assert_eq!(memo(), (1, Some("child")));
+
+        // updated from the root scope
+        set_a(2);
+        assert_eq!(memo(), (2, Some("child")));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn deferred_effect_resolves_context_in_its_own_scope() {
+    use leptos_reactive::{
+        create_isomorphic_effect, provide_context, set_scheduler, use_context,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let queue = Rc::new(RefCell::new(Vec::<Box<dyn FnOnce()>>::new()));
+        set_scheduler(cx, {
+            let queue = Rc::clone(&queue);
+            move |flush| queue.borrow_mut().push(flush)
+        });
+
+        let (a, set_a) = create_signal(cx, 0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _ = cx.run_child_scope({
+            let seen = Rc::clone(&seen);
+            move |cx| {
+                provide_context(cx, "child");
+                let b =
+                    create_memo(cx, move |_| (a(), use_context::<&str>(cx)));
+                create_isomorphic_effect(cx, move |_| {
+                    seen.borrow_mut().push((b(), use_context::<&str>(cx)))
+                });
+            }
+        });
+
+        provide_context(cx, "root");
+        set_a(1);
+        for flush in queue.take() {
+            flush();
+        }
+        assert_eq!(
+            *seen.borrow(),
+            [
+                ((0, Some("child")), Some("child")),
+                ((1, Some("child")), Some("child"))
+            ]
+        );
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn changed_flag_pulses_once_per_flush() {
+    use leptos_reactive::create_isomorphic_effect;
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let (trigger, set_trigger) = create_signal(cx, 0);
+        let (b, changed) =
+            create_memo(cx, move |_| a() * 2).with_changed_flag(cx);
+        let pulses = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let pulses = Rc::clone(&pulses);
+            move |_| pulses.borrow_mut().push(changed())
+        });
+        // reading both the memo and the flag runs once per change
+        let both = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let both = Rc::clone(&both);
+            move |_| both.borrow_mut().push((b(), changed()))
+        });
+
+        // two changes within the same flush
+        create_isomorphic_effect(cx, move |_| {
+            let n = trigger();
+            if n > 0 {
+                set_a(n);
+                set_a(n + 1);
+            }
+        });
+        set_trigger(1);
+        assert_eq!(b(), 4);
+        assert_eq!(*pulses.borrow(), [false, true, false]);
+
+        // no pulse if the memo doesn't change
+        set_a(2);
+        assert_eq!(*pulses.borrow(), [false, true, false]);
+
+        set_a(3);
+        assert_eq!(*pulses.borrow(), [false, true, false, true, false]);
+        assert_eq!(
+            *both.borrow(),
+            [
+                (0, false),
+                (2, true),
+                (4, true),
+                (4, false),
+                (6, true),
+                (6, false)
+            ]
+        );
+        assert!(!changed());
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn changed_flags_are_independent() {
+    use leptos_reactive::create_isomorphic_effect;
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() * 2);
+        let (_, first) = b.with_changed_flag(cx);
+        let first_pulses = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let first_pulses = Rc::clone(&first_pulses);
+            move |_| first_pulses.borrow_mut().push(first())
+        });
+
+        let second_pulses = Rc::new(RefCell::new(Vec::new()));
+        let disposer = cx.child_scope({
+            let second_pulses = Rc::clone(&second_pulses);
+            move |cx| {
+                let (_, second) = b.with_changed_flag(cx);
+                create_isomorphic_effect(cx, move |_| {
+                    second_pulses.borrow_mut().push(second())
+                });
+            }
+        });
+
+        set_a(1);
+        assert_eq!(*first_pulses.borrow(), [false, true, false]);
+        assert_eq!(*second_pulses.borrow(), [false, true, false]);
+
+        // the second flag stops with its scope, and the first keeps going
+        disposer.dispose();
+        set_a(2);
+        assert_eq!(*first_pulses.borrow(), [false, true, false, true, false]);
+        assert_eq!(*second_pulses.borrow(), [false, true, false]);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn changed_future_resolves_with_next_value() {
+    use futures::executor::block_on;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() * 2);
+
+        // the change happens before the future is first polled
+        let next = b.changed(cx);
+        set_a(1);
+        set_a(2);
+        assert_eq!(block_on(next), Some(2));
+
+        // an update that doesn't change the memo doesn't resolve it
+        let next = b.changed(cx);
+        set_a(2);
+        set_a(3);
+        assert_eq!(block_on(next), Some(6));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn changed_future_resolves_to_none_once_disposed() {
+    use futures::executor::block_on;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, _) = create_signal(cx, 0);
+
+        // the memo's scope is disposed
+        let (child, disposer) = cx.run_child_scope(|child| child);
+        let b = create_memo(child, move |_| a() * 2);
+        let next = b.changed(cx);
+        disposer.dispose();
+        assert_eq!(block_on(next), None);
+
+        // the scope that is waiting is disposed
+        let b = create_memo(cx, move |_| a() * 2);
+        let (child, disposer) = cx.run_child_scope(|child| child);
+        let next = b.changed(child);
+        disposer.dispose();
+        assert_eq!(block_on(next), None);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[cfg(debug_assertions)]
+#[test]
+fn changed_future_does_not_subscribe() {
+    create_scope(create_runtime(), |cx| {
+        let (a, _) = create_signal(cx, 0);
+        let b = create_memo(cx, move |_| a() * 2);
+
+        let next = b.changed(cx);
+        assert!(b.subscribers().is_empty());
+        drop(next);
+        assert!(b.subscribers().is_empty());
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_mut_keeps_state_between_runs() {
+    use leptos_reactive::create_memo_mut;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+
+        // counts the real changes of `a`'s parity
+        let mut changes = 0;
+        let mut last = None;
+        let b = create_memo_mut(cx, move |_| {
+            let parity = a() % 2;
+            if last != Some(parity) {
+                changes += 1;
+                last = Some(parity);
+            }
+            changes
+        });
+        assert_eq!(b(), 1);
+
+        set_a(2);
+        assert_eq!(b(), 1);
+        set_a(3);
+        assert_eq!(b(), 2);
+        set_a(5);
+        assert_eq!(b(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn map_with_prev_sees_previous_source_value() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let b = create_memo(cx, move |_| a() * 10);
+        let pairs = b.map_with_prev(|prev, new| (prev.copied(), *new));
+        assert_eq!(pairs(), (None, 10));
+
+        set_a(2);
+        assert_eq!(pairs(), (Some(10), 20));
+        set_a(5);
+        assert_eq!(pairs(), (Some(20), 50));
+
+        // the source didn't change, so neither does the previous value
+        set_a(5);
+        assert_eq!(pairs(), (Some(20), 50));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn switch_memo_only_tracks_active_branch() {
+    use leptos_reactive::{create_isomorphic_effect, create_switch_memo};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (flag, set_flag) = create_signal(cx, true);
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, 2);
+        let a = create_memo(cx, move |_| a());
+        let b = create_memo(cx, move |_| b());
+        let switched = create_switch_memo(cx, move || flag(), a, b);
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                switched();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(switched(), 1);
+
+        // changes to the inactive branch are ignored
+        set_b(3);
+        assert_eq!(runs.get(), 1);
+
+        set_flag(false);
+        assert_eq!(switched(), 3);
+        assert_eq!(runs.get(), 2);
+        set_a(4);
+        assert_eq!(runs.get(), 2);
+        set_b(5);
+        assert_eq!(switched(), 5);
+        assert_eq!(runs.get(), 3);
+
+        // flipping back re-subscribes to the first branch exactly once
+        set_flag(true);
+        assert_eq!(switched(), 4);
+        assert_eq!(runs.get(), 4);
+        set_a(6);
+        assert_eq!(runs.get(), 5);
+        #[cfg(debug_assertions)]
+        {
+            assert_eq!(a.subscribers(), vec![switched.node_id()]);
+            assert!(b.subscribers().is_empty());
+        }
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_version_counts_real_changes() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let b = create_memo(cx, move |_| a() / 10);
+        assert_eq!(b.version(), 0);
+
+        set_a(2);
+        assert_eq!(b.version(), 0);
+        set_a(10);
+        assert_eq!(b.version(), 1);
+        let version = b.version();
+        set_a(11);
+        assert!(!b.changed_since(version));
+        set_a(25);
+        assert_eq!(b.version(), 2);
+        assert!(b.changed_since(version));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[cfg(not(any(feature = "csr", feature = "hydrate", feature = "ssr")))]
+#[test]
+fn resource_memo_transitions() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_resource_memo, AsyncState,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (id, set_id) = create_signal(cx, 1);
+        let user = create_resource_memo(
+            cx,
+            move || id(),
+            |id: i32| async move {
+                if id > 0 {
+                    Ok(id / 10)
+                } else {
+                    Err("invalid id")
+                }
+            },
+        );
+        let states = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let states = Rc::clone(&states);
+            move |_| states.borrow_mut().push(user())
+        });
+        assert_eq!(*states.borrow(), [AsyncState::Ready(0)]);
+
+        set_id(12);
+        assert_eq!(
+            *states.borrow(),
+            [
+                AsyncState::Ready(0),
+                AsyncState::Loading,
+                AsyncState::Ready(1)
+            ]
+        );
+
+        set_id(-1);
+        assert_eq!(user(), AsyncState::Failed("invalid id"));
+        assert_eq!(states.borrow().len(), 5);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[cfg(feature = "ssr")]
+#[test]
+fn resource_memo_cancels_in_flight_fetches() {
+    use futures::channel::oneshot;
+    use leptos_reactive::{
+        create_resource_memo, raw_scope_and_disposer, AsyncState,
+    };
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let local = tokio::task::LocalSet::new();
+    local.block_on(&rt, async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        let (id, set_id) = create_signal(cx, 1);
+        let senders = Rc::new(RefCell::new(HashMap::new()));
+        let user = create_resource_memo(cx, move || id(), {
+            let senders = Rc::clone(&senders);
+            move |id: i32| {
+                let (tx, rx) = oneshot::channel::<i32>();
+                senders.borrow_mut().insert(id, tx);
+                async move { rx.await.map_err(|_| ()) }
+            }
+        });
+        assert_eq!(user(), AsyncState::Loading);
+
+        // the source changes twice before the first fetch completes
+        set_id(2);
+        set_id(3);
+        let tx = senders.borrow_mut().remove(&1).unwrap();
+        _ = tx.send(10);
+        tokio::task::yield_now().await;
+        assert_eq!(user(), AsyncState::Loading);
+
+        let tx = senders.borrow_mut().remove(&3).unwrap();
+        _ = tx.send(30);
+        tokio::task::yield_now().await;
+        assert_eq!(user(), AsyncState::Ready(30));
+
+        // an earlier fetch completing late doesn't replace the result
+        let tx = senders.borrow_mut().remove(&2).unwrap();
+        _ = tx.send(20);
+        tokio::task::yield_now().await;
+        assert_eq!(user(), AsyncState::Ready(30));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn client_only_memo() {
+    use leptos_reactive::create_memo_client_only;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let runs = Rc::new(Cell::new(0));
+        let b = create_memo_client_only(cx, -1, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                a() * 2
+            }
+        });
+        set_a(2);
+
+        if cfg!(feature = "ssr") {
+            // the server never runs the computation
+            assert_eq!(b(), -1);
+            assert_eq!(runs.get(), 0);
+        } else {
+            assert_eq!(b(), 4);
+            assert_eq!(runs.get(), 2);
+        }
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn inspect_sees_only_real_changes() {
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let parity = create_memo(cx, move |_| a() % 2).inspect({
+            let seen = Rc::clone(&seen);
+            move |value| seen.borrow_mut().push(*value)
+        });
+
+        // the initial value is not reported
+        assert!(seen.borrow().is_empty());
+        set_a(3);
+        assert!(seen.borrow().is_empty());
+        set_a(4);
+        set_a(6);
+        set_a(7);
+        assert_eq!(*seen.borrow(), [0, 1]);
+
+        // inspecting doesn't subscribe to the memo
+        #[cfg(debug_assertions)]
+        assert!(parity.subscribers().is_empty());
+        assert_eq!(parity(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn inspector_is_dropped_with_its_memo() {
+    use std::rc::Rc;
+
+    create_scope(create_runtime(), |cx| {
+        let captured = Rc::new(());
+        cx.child_scope({
+            let captured = Rc::clone(&captured);
+            move |cx| {
+                create_memo(cx, |_| 1).inspect(move |_| _ = &captured);
+            }
+        })
+        .dispose();
+        assert_eq!(Rc::strong_count(&captured), 1);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), debug_assertions))]
+#[test]
+fn lookup_value_by_node_id() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let a = a.debuggable();
+        let b = create_memo(cx, move |_| format!("a = {}", a())).debuggable();
+        #[derive(PartialEq)]
+        struct NotDebug;
+        let c = create_memo(cx, move |_| {
+            a();
+            NotDebug
+        });
+
+        set_a(2);
+        assert_eq!(cx.lookup_value(a.node_id()).as_deref(), Some("2"));
+        assert_eq!(cx.lookup_value(b.node_id()).as_deref(), Some("\"a = 2\""));
+        assert_eq!(cx.lookup_value(c.node_id()).as_deref(), Some("<opaque>"));
+
+        // ids found in the graph can be looked up too
+        let deps = b.dependencies();
+        assert_eq!(cx.lookup_value(deps[0]).as_deref(), Some("2"));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn fold_memo_accumulates_source_changes() {
+    use leptos_reactive::{create_fold_memo, create_isomorphic_effect};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let folds = Rc::new(Cell::new(0));
+        let max = create_fold_memo(cx, 0, a, {
+            let folds = Rc::clone(&folds);
+            move |max, a| {
+                folds.set(folds.get() + 1);
+                a.max(*max)
+            }
+        });
+        let notified = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let notified = Rc::clone(&notified);
+            move |_| {
+                max();
+                notified.set(notified.get() + 1);
+            }
+        });
+
+        // reading doesn't fold
+        assert_eq!(max(), 1);
+        assert_eq!(max(), 1);
+        assert_eq!(folds.get(), 1);
+
+        set_a(5);
+        set_a(3);
+        assert_eq!(max(), 5);
+        assert_eq!(folds.get(), 3);
+        // folding 3 into 5 leaves the accumulator unchanged
+        assert_eq!(notified.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn deferred_memo_coalesces_until_the_next_flush() {
+    use leptos_reactive::{
+        create_deferred_memo, create_isomorphic_effect, set_scheduler,
+        SignalGetUntracked,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let queue = Rc::new(RefCell::new(Vec::<Box<dyn FnOnce()>>::new()));
+        set_scheduler(cx, {
+            let queue = Rc::clone(&queue);
+            move |flush| queue.borrow_mut().push(flush)
+        });
+
+        let (a, set_a) = create_signal(cx, 1);
+        let computed = Rc::new(RefCell::new(Vec::new()));
+        let deferred = create_deferred_memo(cx, {
+            let computed = Rc::clone(&computed);
+            move |_| {
+                computed.borrow_mut().push(a());
+                a() * 10
+            }
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let seen = Rc::clone(&seen);
+            move |_| seen.borrow_mut().push(deferred())
+        });
+
+        set_a(2);
+        set_a(3);
+        // computed promptly, but not yet passed on
+        assert_eq!(*computed.borrow(), [1, 2, 3]);
+        assert_eq!(deferred.get_untracked(), 10);
+        assert!(deferred.is_dirty());
+
+        for flush in queue.take() {
+            flush();
+        }
+        assert_eq!(deferred.get_untracked(), 30);
+        assert!(!deferred.is_dirty());
+        // its own dependents run in the flush after that, like any others
+        for flush in queue.take() {
+            flush();
+        }
+        assert_eq!(*seen.borrow(), [10, 30]);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn try_with_result_distinguishes_failures() {
+    use leptos_reactive::{
+        create_owning_memo, Memo, SignalError, SignalGet, SignalWith,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    let runtime = create_runtime();
+    let this = Rc::new(Cell::new(None::<Memo<i32>>));
+    let seen = Rc::new(Cell::new(None));
+    _ = create_scope(runtime, {
+        let this = Rc::clone(&this);
+        let seen = Rc::clone(&seen);
+        move |cx| {
+            let (a, set_a) = create_signal(cx, 1);
+            let owning = create_owning_memo(cx, {
+                let this = Rc::clone(&this);
+                move |_| {
+                    // the previous value has been taken while computing
+                    if let Some(this) = this.get() {
+                        seen.set(Some(cx.untrack(|| this.try_get_result())));
+                    }
+                    (a(), true)
+                }
+            });
+            this.set(Some(owning));
+            set_a(2);
+        }
+    });
+    let memo = this.get().unwrap();
+    assert_eq!(seen.take(), Some(Err(SignalError::Uninitialized)));
+    assert_eq!(memo.try_with_result(|n| *n), Ok(2));
+    assert_eq!(memo.try_get(), Some(2));
+
+    runtime.dispose();
+    assert_eq!(memo.try_get_result(), Err(SignalError::RuntimeDisposed));
+    assert_eq!(memo.try_with(|n| *n), None);
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn map_ref_clones_only_the_borrowed_part() {
+    use leptos_reactive::create_isomorphic_effect;
+    use std::{cell::Cell, rc::Rc};
+
+    // cloning the whole value would fail the test
+    #[derive(PartialEq)]
+    struct Big {
+        id: u32,
+        rows: Vec<u32>,
+    }
+    impl Clone for Big {
+        fn clone(&self) -> Self {
+            panic!("the whole value should never be cloned")
+        }
+    }
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let big = create_memo(cx, move |_| Big {
+            id: 7,
+            rows: vec![a()],
+        });
+        let id = big.map_ref(cx, |big| &big.id);
+        let first = big.map_ref(cx, |big| &big.rows[0]);
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                id();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        set_a(1);
+        assert_eq!(first(), 1);
+        assert_eq!(id(), 7);
+        // the id didn't change, so its dependents didn't run again
+        assert_eq!(runs.get(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_is_disposed_with_its_scope() {
+    use leptos_reactive::{Memo, SignalError};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let runs = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut memo = None::<Memo<i32>>;
+        cx.child_scope(|child| {
+            let runs = std::rc::Rc::clone(&runs);
+            memo = Some(create_memo(child, move |_| {
+                runs.set(runs.get() + 1);
+                a() * 10
+            }));
+        })
+        .dispose();
+
+        // neither the value nor the computation outlives the scope
+        assert_eq!(memo.unwrap().try_get_result(), Err(SignalError::Disposed));
+        set_a(2);
+        assert_eq!(runs.get(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn reparented_memo_follows_its_new_scope() {
+    use leptos_reactive::{
+        create_isomorphic_effect, Memo, SignalError, SignalGetUntracked,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let seen = Rc::new(Cell::new(0));
+        let (parent, parent_disposer) = cx.run_child_scope(|parent| parent);
+
+        let mut memos = None::<(Memo<i32>, Memo<i32>)>;
+        cx.child_scope(|child| {
+            let kept = create_memo(child, move |_| a() * 10);
+            let dropped = create_memo(child, move |_| a() * 100);
+            kept.reparent(parent);
+            create_isomorphic_effect(parent, {
+                let seen = Rc::clone(&seen);
+                move |_| seen.set(kept())
+            });
+            memos = Some((kept, dropped));
+        })
+        .dispose();
+        let (kept, dropped) = memos.unwrap();
+
+        // only the reparented memo survives the child scope
+        assert_eq!(dropped.try_get_result(), Err(SignalError::Disposed));
+        set_a(2);
+        assert_eq!(kept.get_untracked(), 20);
+        assert_eq!(seen.get(), 20);
+
+        parent_disposer.dispose();
+        assert_eq!(kept.try_get_result(), Err(SignalError::Disposed));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+#[should_panic(expected = "scope that has been disposed")]
+fn reparent_into_disposed_scope_panics() {
+    create_scope(create_runtime(), |cx| {
+        let (gone, disposer) = cx.run_child_scope(|gone| gone);
+        disposer.dispose();
+        let memo = create_memo(cx, |_| 1);
+        memo.reparent(gone);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn for_each_drops_once_with_a_pending_flush() {
+    use leptos_reactive::set_scheduler;
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    create_scope(create_runtime(), |cx| {
+        let queue = Rc::new(RefCell::new(Vec::<Box<dyn FnOnce()>>::new()));
+        set_scheduler(cx, {
+            let queue = Rc::clone(&queue);
+            move |flush| queue.borrow_mut().push(flush)
+        });
+
+        let (a, set_a) = create_signal(cx, 1);
+        let double = create_memo(cx, move |_| a() * 2);
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let drops = Rc::new(Cell::new(0));
+
+        let disposer = cx.child_scope(|cx| {
+            let values = Rc::clone(&values);
+            let drops = Rc::clone(&drops);
+            double.for_each(
+                cx,
+                move |value| values.borrow_mut().push(*value),
+                move || drops.set(drops.get() + 1),
+            );
+        });
+        assert_eq!(*values.borrow(), [2]);
+
+        // the update is waiting for a flush when the scope is disposed
+        set_a(2);
+        disposer.dispose();
+        assert_eq!(drops.get(), 1);
+
+        for flush in queue.take() {
+            flush();
+        }
+        assert_eq!(*values.borrow(), [2]);
+        assert_eq!(drops.get(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn eq_by_memo_notifies_only_when_the_key_changes() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_memo_eq_by, SignalGetUntracked,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (record, set_record) = create_signal(cx, (1, "draft"));
+        let memo = create_memo_eq_by(cx, |(id, _)| *id, move |_| record());
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                memo();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        set_record((1, "published"));
+        // the full value is stored, but nobody is told
+        assert_eq!(memo.get_untracked(), (1, "published"));
+        assert_eq!(runs.get(), 1);
+
+        set_record((2, "published"));
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn throttled_memo_runs_one_trailing_computation() {
+    use leptos_reactive::{
+        create_throttled_memo, raw_scope_and_disposer, SignalGetUntracked,
+    };
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+    use tokio::time::sleep;
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    let local = tokio::task::LocalSet::new();
+    local.block_on(&rt, async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        let (a, set_a) = create_signal(cx, 1);
+        let runs = Rc::new(RefCell::new(Vec::new()));
+        let memo = create_throttled_memo(cx, Duration::from_millis(50), {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.borrow_mut().push(a());
+                a() * 10
+            }
+        });
+
+        // a burst of changes within the interval isn't computed
+        assert!(!memo.is_dirty());
+        set_a(2);
+        set_a(3);
+        assert_eq!(memo.get_untracked(), 10);
+        assert!(memo.is_dirty());
+
+        // the trailing run sees the latest value, with no further changes
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(memo.get_untracked(), 30);
+        assert!(!memo.is_dirty());
+        assert_eq!(*runs.borrow(), [1, 3]);
+
+        // once the interval has passed, a change is computed right away
+        sleep(Duration::from_millis(100)).await;
+        set_a(4);
+        assert_eq!(memo.get_untracked(), 40);
+        assert_eq!(*runs.borrow(), [1, 3, 4]);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(
+    not(feature = "stable"),
+    not(any(feature = "csr", feature = "hydrate", feature = "ssr"))
+))]
+#[test]
+fn throttled_memo_has_no_cooldown_without_an_async_runtime() {
+    use leptos_reactive::create_throttled_memo;
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let runs = Rc::new(Cell::new(0));
+        let memo = create_throttled_memo(cx, Duration::from_secs(60), {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                a() * 10
+            }
+        });
+
+        set_a(2);
+        set_a(3);
+        assert_eq!(memo(), 30);
+        assert_eq!(runs.get(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn standalone_memo_tracks_standalone_signals() {
+    use leptos_reactive::{
+        create_memo_standalone, create_signal_standalone, SignalError,
+    };
+
+    let ((a, set_a), a_guard) = create_signal_standalone(1);
+    let (double, double_guard) = create_memo_standalone(move |_| a() * 2);
+    assert_eq!(double(), 2);
+    set_a(5);
+    assert_eq!(double(), 10);
+
+    // dropping a guard disposes of its node, like disposing its scope
+    drop(double_guard);
+    assert_eq!(double.try_get_result(), Err(SignalError::Disposed));
+
+    // the runtime goes away with the last guard
+    drop(a_guard);
+    assert_eq!(double.try_get_result(), Err(SignalError::RuntimeDisposed));
+
+    // and a new one is created for the next standalone node
+    let (three, _guard) = create_memo_standalone(|_| 3);
+    assert_eq!(three(), 3);
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn diff_from_shows_values_side_by_side() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, vec![1, 2]);
+        let b = create_memo(cx, move |_| a());
+        let c = create_memo(cx, move |_| vec![1, 2]);
+
+        b.assert_eq_untracked(&vec![1, 2]);
+        assert_eq!(b.diff_from(&c), None);
+
+        set_a(vec![1, 3]);
+        let diff = b.diff_from(&c).unwrap();
+        let lines = diff.lines().collect::<Vec<_>>();
+        assert!(lines[0].contains(&b.node_id().to_string()));
+        assert!(lines[0].contains(&c.node_id().to_string()));
+        // only the element that changed is marked
+        let marked = lines
+            .iter()
+            .filter(|line| line.starts_with('>'))
+            .collect::<Vec<_>>();
+        assert_eq!(marked.len(), 1);
+        assert!(marked[0].contains('3') && marked[0].contains('2'));
+
+        // equality comes from PartialEq, not from the printed values
+        let nan = create_memo(cx, |_| f64::NAN);
+        assert!(nan.diff_from(&nan).is_some());
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+#[should_panic(expected = "does not have the expected value")]
+fn assert_eq_untracked_panics_on_mismatch() {
+    create_scope(create_runtime(), |cx| {
+        let b = create_memo(cx, |_| 1);
+        b.assert_eq_untracked(&2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn multi_keyed_memo_caches_each_key_combination() {
+    use leptos_reactive::{create_isomorphic_effect, create_multi_keyed_memo};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, 10);
+        let runs = Rc::new(Cell::new(0));
+        let sum = create_multi_keyed_memo(cx, (a.into(), b.into()), {
+            let runs = Rc::clone(&runs);
+            move |a, b| {
+                runs.set(runs.get() + 1);
+                a + b
+            }
+        });
+        let seen = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let seen = Rc::clone(&seen);
+            move |_| seen.set(sum())
+        });
+
+        set_a(2);
+        set_b(20);
+        assert_eq!(runs.get(), 3);
+        assert_eq!(seen.get(), 22);
+
+        // each combination seen before comes from the cache
+        set_b(10);
+        set_a(1);
+        set_b(20);
+        assert_eq!(runs.get(), 4);
+        assert_eq!(seen.get(), 21);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn shared_memos_are_shared_per_key_and_scope() {
+    use leptos_reactive::create_shared_memo;
+
+    create_scope(create_runtime(), |cx| {
+        let (count, _) = create_signal(cx, 1);
+        let double = |cx| {
+            create_shared_memo(cx, "double", move || {
+                create_memo(cx, move |_| count() * 2)
+            })
+        };
+        let triple = create_shared_memo(cx, "triple", move || {
+            create_memo(cx, move |_| count() * 3)
+        });
+
+        let shared = double(cx);
+        assert_eq!(double(cx).id(), shared.id());
+        assert_ne!(triple.id(), shared.id());
+
+        // a child scope keeps its own registry
+        cx.child_scope(|child| {
+            let in_child = double(child);
+            assert_ne!(in_child.id(), shared.id());
+            assert_eq!(double(child).id(), in_child.id());
+        })
+        .dispose();
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn panicking_memo_can_compute_again() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let memo = create_memo(cx, move |_| {
+            let a = a();
+            assert_ne!(a, 2, "the memo can't handle 2");
+            a
+        });
+
+        assert!(catch_unwind(AssertUnwindSafe(|| set_a(2))).is_err());
+
+        // the failed run didn't leave the memo marked as still computing
+        set_a(3);
+        assert_eq!(memo(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), feature = "hydrate"))]
+#[test]
+fn memo_hydrates_from_its_serialized_value() {
+    use leptos_reactive::{hydrate_memo_from, run_scope, SignalGetUntracked};
+    use std::{cell::Cell, rc::Rc};
+
+    // the server renders in its own runtime, with its own IDs
+    let (id, json) = std::thread::spawn(|| {
+        run_scope(create_runtime(), |cx| {
+            let (count, _) = create_signal(cx, 3);
+            let double = create_memo(cx, move |_| count() * 2);
+            (double.id(), double.serialize().unwrap())
+        })
+    })
+    .join()
+    .unwrap();
+
+    let client = create_runtime();
+    run_scope(client, move |cx| {
+        let runs = Rc::new(Cell::new(0));
+        hydrate_memo_from::<i32>(cx, id, &json).unwrap();
+        // the client's state has diverged from the server's
+        let (count, _) = create_signal(cx, 4);
+        let double = create_memo(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                count() * 2
+            }
+        });
+        assert_eq!(double.id(), id);
+
+        // outside the browser, the memo catches up with the client at once
+        assert_eq!(double.get_untracked(), 8);
+        assert_eq!(runs.get(), 1);
+    });
+    client.dispose();
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_with_initial_only_runs_when_its_deps_change() {
+    use leptos_reactive::{create_memo_with_initial, SignalGetUntracked};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, 0);
+        let (_, set_unrelated) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+        let sum = create_memo_with_initial(cx, 0, move || a(), {
+            let runs = Rc::clone(&runs);
+            move |a, _| {
+                runs.set(runs.get() + 1);
+                a + b()
+            }
+        });
+
+        set_unrelated(1);
+        assert_eq!(runs.get(), 0);
+        assert_eq!(sum.get_untracked(), 0);
+
+        set_a(2);
+        assert_eq!(runs.get(), 1);
+        assert_eq!(sum.get_untracked(), 2);
+
+        // `f` reads `b`, but only `deps` is tracked
+        set_b(10);
+        assert_eq!(runs.get(), 1);
+        set_a(3);
+        assert_eq!(sum.get_untracked(), 13);
+    })
+    .dispose()
+}
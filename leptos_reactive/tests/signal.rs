@@ -28,3 +28,307 @@ fn derived_signals() {
     })
     .dispose()
 }
+
+#[cfg(all(not(feature = "stable"), not(feature = "ssr"), debug_assertions))]
+#[test]
+fn scheduler_stats_count_flushes_and_nodes() {
+    use leptos_reactive::{create_effect, create_memo};
+
+    let runtime = create_runtime();
+    create_scope(runtime, |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let double = create_memo(cx, move |_| a() * 2);
+        create_effect(cx, move |_| {
+            double();
+        });
+
+        set_a(1);
+        set_a(2);
+        set_a(3);
+    })
+    .dispose();
+
+    let stats = runtime.scheduler_stats();
+    assert_eq!(stats.flushes, 3);
+    // each flush runs the memo, then the effect
+    assert_eq!(stats.average_nodes_per_flush, 2.0);
+    // the effect runs within the memo's update, within the update to `a`
+    assert_eq!(stats.max_flush_depth, 2);
+    runtime.dispose();
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn snapshot_restores_serializable_signals() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_memo, create_serializable_signal,
+        SignalWith,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_serializable_signal(cx, 1);
+        let (b, set_b) = create_serializable_signal(cx, "one".to_string());
+        let (_, set_c) = create_signal(cx, 1);
+        let len = create_memo(cx, move |_| b.with(String::len));
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                a();
+                b();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        let snapshot = cx.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.skipped().len(), 1);
+
+        set_a(2);
+        set_b("three".to_string());
+        set_c(2);
+        assert_eq!(len(), 5);
+        assert_eq!(runs.get(), 3);
+
+        cx.restore(&snapshot).unwrap();
+        assert_eq!(a(), 1);
+        assert_eq!(b(), "one");
+        assert_eq!(len(), 3);
+        // both signals changed, but the effect is only notified once
+        assert_eq!(runs.get(), 4);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn binding_ignores_writes_fed_back_during_a_write() {
+    use leptos_reactive::{create_binding, create_isomorphic_effect};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let writes = Rc::new(Cell::new(0));
+        let binding = create_binding(cx, a, {
+            let writes = Rc::clone(&writes);
+            move |value| {
+                writes.set(writes.get() + 1);
+                set_a(value);
+            }
+        });
+
+        // an effect that writes a new value back whenever the binding changes
+        // would otherwise never stop
+        create_isomorphic_effect(cx, move |_| binding.set(binding.get() + 1));
+        assert_eq!(writes.get(), 1);
+        assert_eq!(binding.get(), 1);
+
+        binding.set(10);
+        assert_eq!(writes.get(), 2);
+        assert_eq!(a(), 10);
+
+        // setting the current value does not write
+        binding.set(10);
+        assert_eq!(writes.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn binding_writes_again_after_a_write_panics() {
+    use leptos_reactive::create_binding;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let binding = create_binding(cx, a, move |value| {
+            if value < 0 {
+                panic!("negative value");
+            }
+            set_a(value);
+        });
+
+        assert!(catch_unwind(AssertUnwindSafe(|| binding.set(-1))).is_err());
+        binding.set(1);
+        assert_eq!(a(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(all(not(feature = "stable"), not(feature = "ssr")))]
+#[test]
+fn signal_stream_combinators() {
+    use futures::{executor::block_on, StreamExt};
+    use leptos_reactive::{create_memo, SignalStreamExt};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let b = create_memo(cx, move |_| a() * 10);
+        let mapped = b.map_stream(cx, |n| n.to_string());
+        let evens = a.filter_stream(cx, |n| n % 2 == 0);
+        let sums = a.scan_stream(cx, 0, |sum, n| sum + n);
+
+        for n in 2..=4 {
+            set_a(n);
+        }
+
+        assert_eq!(
+            block_on(mapped.take(4).collect::<Vec<_>>()),
+            ["10", "20", "30", "40"]
+        );
+        assert_eq!(block_on(evens.take(2).collect::<Vec<_>>()), [2, 4]);
+        assert_eq!(block_on(sums.take(4).collect::<Vec<_>>()), [1, 3, 6, 10]);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn stepper_stays_within_its_steps() {
+    use leptos_reactive::{create_memo, create_stepper};
+
+    create_scope(create_runtime(), |cx| {
+        let stepper = create_stepper(cx, 2);
+        let current = stepper.current();
+        let label = create_memo(cx, move |_| format!("step {}", current() + 1));
+        assert_eq!(label(), "step 1");
+
+        stepper.prev();
+        assert_eq!(stepper.current()(), 0);
+
+        stepper.next();
+        assert_eq!(label(), "step 2");
+        assert!(!stepper.can_next()());
+        assert!(stepper.can_prev()());
+
+        stepper.next();
+        assert_eq!(stepper.current()(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn stepper_with_one_step_cannot_move() {
+    use leptos_reactive::create_stepper;
+
+    create_scope(create_runtime(), |cx| {
+        let stepper = create_stepper(cx, 1);
+        assert!(!stepper.can_next()());
+        assert!(!stepper.can_prev()());
+        stepper.next();
+        stepper.prev();
+        assert_eq!(stepper.current()(), 0);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+#[should_panic(expected = "at least one step")]
+fn stepper_with_no_steps_panics() {
+    use leptos_reactive::create_stepper;
+
+    create_scope(create_runtime(), |cx| {
+        create_stepper(cx, 0);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn deferred_commit_only_notifies_on_commit() {
+    use leptos_reactive::{
+        create_deferred_commit, create_isomorphic_effect, SignalSet,
+        SignalUpdate,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let count = create_deferred_commit(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            let committed = count.committed();
+            move |_| {
+                committed();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        count.draft().set(1);
+        count.draft().update(|n| *n += 1);
+        assert_eq!(count.committed()(), 0);
+        assert_eq!(runs.get(), 1);
+
+        count.commit();
+        assert_eq!(count.committed()(), 2);
+        assert_eq!(runs.get(), 2);
+
+        count.draft().set(5);
+        count.revert();
+        assert_eq!(count.draft()(), 2);
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn sample_on_captures_value_each_time_trigger_fires() {
+    use leptos_reactive::{create_isomorphic_effect, sample_on};
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (value, set_value) = create_signal(cx, "a");
+        let (trigger, fire) = create_signal(cx, ());
+        let sampled = sample_on(cx, value.into(), trigger.into());
+        let log = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(sampled())
+        });
+
+        set_value("b");
+        set_value("c");
+        fire(());
+        // every firing samples again, even if the value is the same
+        fire(());
+        set_value("d");
+
+        assert_eq!(*log.borrow(), ["a", "c", "c"]);
+        assert_eq!(sampled(), "c");
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn restore_runs_effects_on_a_signal_and_its_memo_once() {
+    use leptos_reactive::{
+        create_isomorphic_effect, create_memo, create_serializable_signal,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_serializable_signal(cx, 1);
+        let double = create_memo(cx, move |_| a() * 2);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push((a(), double()))
+        });
+
+        let snapshot = cx.snapshot();
+        set_a(2);
+        log.borrow_mut().clear();
+
+        cx.restore(&snapshot).unwrap();
+        // the effect sees the restored signal and memo together, once
+        assert_eq!(*log.borrow(), [(1, 2)]);
+    })
+    .dispose()
+}